@@ -0,0 +1,191 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Config,
+    data::{load_json, save_json},
+    i18n::Msg,
+    prompt::{error_text, success_text},
+    work::WorkConfig,
+};
+
+const ABSENCES_FILE: &str = "absences.json";
+
+/// Kind of a day off. The server does not know absences, they are kept in
+/// the data directory.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Vacation,
+    Sick,
+    /// A day off taken from the overtime, which is not credited.
+    Comp,
+}
+
+impl Kind {
+    /// Whether the target of the absent days counts as worked.
+    fn credited(self) -> bool {
+        matches!(self, Kind::Vacation | Kind::Sick)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Absence {
+    pub id: u32,
+    pub kind: Kind,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Only half of each day is taken off.
+    #[serde(default)]
+    pub half: bool,
+}
+
+impl Absence {
+    fn contains(&self, day: NaiveDate) -> bool {
+        (self.from..=self.to).contains(&day)
+    }
+
+    /// `½` for half days, used in lists and reports.
+    pub fn label(&self) -> String {
+        match self.half {
+            true => format!("{} ½", Msg::AbsenceKind(self.kind)),
+            false => Msg::AbsenceKind(self.kind).to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Absences {
+    absences: Vec<Absence>,
+}
+
+impl Absences {
+    pub fn load() -> Absences {
+        load_json(ABSENCES_FILE, |path, err| Msg::AbsencesError(path, err))
+    }
+
+    pub fn ids(&self) -> Vec<u32> {
+        self.absences.iter().map(|absence| absence.id).collect()
+    }
+
+    /// The absence covering a day.
+    pub fn on(&self, day: NaiveDate) -> Option<&Absence> {
+        self.absences.iter().find(|absence| absence.contains(day))
+    }
+
+    /// Part of the day's target that counts as worked because of an absence.
+    pub fn credited_on(&self, day: NaiveDate, work: &WorkConfig) -> chrono::Duration {
+        match self.on(day) {
+            Some(absence) if absence.kind.credited() && absence.half => work.target_on(day) / 2,
+            Some(absence) if absence.kind.credited() => work.target_on(day),
+            _ => chrono::Duration::zero(),
+        }
+    }
+
+    /// The target of a day that is left to be worked after absences.
+    pub fn open_target(&self, day: NaiveDate, work: &WorkConfig) -> chrono::Duration {
+        work.target_on(day) - self.credited_on(day, work)
+    }
+
+    /// Vacation days taken in a year, weekends and public holidays do not
+    /// count.
+    fn vacation_days(&self, year: i32, work: &WorkConfig) -> f64 {
+        self.absences
+            .iter()
+            .filter(|absence| absence.kind == Kind::Vacation)
+            .flat_map(|absence| {
+                absence
+                    .from
+                    .iter_days()
+                    .take_while(|day| *day <= absence.to)
+                    .filter(|day| {
+                        day.year() == year && work.target_on(*day) > chrono::Duration::zero()
+                    })
+                    .map(|_| if absence.half { 0.5 } else { 1.0 })
+            })
+            .sum()
+    }
+}
+
+/// Records an absence from `from` to `to`, both inclusive.
+pub fn add(kind: Kind, from: NaiveDate, to: NaiveDate, half: bool) -> bool {
+    let mut absences = Absences::load();
+
+    if let Some(overlapping) = absences
+        .absences
+        .iter()
+        .find(|absence| absence.from <= to && from <= absence.to)
+    {
+        println!(
+            "{}",
+            error_text(&Msg::AbsenceOverlaps(overlapping.id).to_string())
+        );
+        return false;
+    }
+
+    let id = absences
+        .absences
+        .iter()
+        .map(|absence| absence.id)
+        .max()
+        .unwrap_or(0)
+        + 1;
+    absences.absences.push(Absence {
+        id,
+        kind,
+        from,
+        to,
+        half,
+    });
+    absences.absences.sort_by_key(|absence| absence.from);
+
+    save(&absences, Msg::AbsenceAdded(id))
+}
+
+pub fn delete(id: u32) -> bool {
+    let mut absences = Absences::load();
+
+    let count = absences.absences.len();
+    absences.absences.retain(|absence| absence.id != id);
+    if absences.absences.len() == count {
+        println!("{}", error_text(&Msg::AbsenceNotFound.to_string()));
+        return false;
+    }
+
+    save(&absences, Msg::AbsenceDeleted)
+}
+
+fn save(absences: &Absences, success: Msg) -> bool {
+    save_json(ABSENCES_FILE, absences, Some(success))
+}
+
+/// Prints all absences of the current year and the vacation days left.
+pub fn print_list(config: &Config) -> bool {
+    let absences = Absences::load();
+    let year = config.display.today().year();
+
+    println!("{}", success_text(&Msg::AbsencesTitle(year).to_string()));
+    for absence in absences
+        .absences
+        .iter()
+        .filter(|absence| absence.from.year() <= year && year <= absence.to.year())
+    {
+        let days = match absence.from == absence.to {
+            true => config.display.day_date(absence.from),
+            false => format!(
+                "{} - {}",
+                config.display.day_date(absence.from),
+                config.display.day_date(absence.to)
+            ),
+        };
+        println!("{:>3}  {days}  {}", absence.id, absence.label());
+    }
+
+    let taken = absences.vacation_days(year, &config.work);
+    println!();
+    match config.work.vacation_days {
+        Some(total) => println!("{}", Msg::VacationLeft(taken, total as f64 - taken, total)),
+        None => println!("{}", Msg::VacationTaken(taken)),
+    }
+    true
+}
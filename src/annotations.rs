@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{
+    block::Block,
+    data::{load_json, save_json},
+    i18n::Msg,
+};
+
+const ANNOTATIONS_FILE: &str = "annotations.json";
+
+/// What the server does not store about a block.
+#[derive(Serialize, Deserialize, Default)]
+struct BlockAnnotation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct PauseAnnotation {
+    note: Option<String>,
+}
+
+/// Projects, tags and notes of the blocks and notes of the pauses, kept in
+/// the data directory by ID and merged into the blocks fetched from the
+/// server.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Annotations {
+    blocks: BTreeMap<i32, BlockAnnotation>,
+    #[serde(default)]
+    pauses: BTreeMap<i32, PauseAnnotation>,
+}
+
+impl Annotations {
+    pub fn load() -> Annotations {
+        load_json(ANNOTATIONS_FILE, |path, err| {
+            Msg::AnnotationsError(path, err)
+        })
+    }
+
+    /// Copies the project, tags and notes into a block fetched from the
+    /// server.
+    pub fn apply(&self, block: &mut Block) {
+        if let Some(annotation) = self.blocks.get(&block.id) {
+            block.project = annotation.project.clone();
+            block.tags = annotation.tags.clone();
+            block.note = annotation.note.clone();
+        }
+        for pause in block.pauses_mut() {
+            if let Some(annotation) = self.pauses.get(&pause.id) {
+                pause.note = annotation.note.clone();
+            }
+        }
+    }
+
+    /// All projects and tags in use, for completion.
+    pub fn projects_and_tags(&self) -> (Vec<String>, Vec<String>) {
+        let mut projects: Vec<String> = Vec::new();
+        let mut tags: Vec<String> = Vec::new();
+        for annotation in self.blocks.values() {
+            if let Some(ref project) = annotation.project {
+                if !projects.contains(project) {
+                    projects.push(project.clone());
+                }
+            }
+            for tag in &annotation.tags {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+        }
+        (projects, tags)
+    }
+}
+
+pub fn set_project(block_id: i32, project: &str) -> bool {
+    let mut annotations = Annotations::load();
+    annotations.blocks.entry(block_id).or_default().project = Some(project.to_string());
+    save(&annotations, Msg::ProjectSet(block_id, project))
+}
+
+pub fn add_tag(block_id: i32, tag: &str) -> bool {
+    let mut annotations = Annotations::load();
+    let tags = &mut annotations.blocks.entry(block_id).or_default().tags;
+    if !tags.iter().any(|existing| existing == tag) {
+        tags.push(tag.to_string());
+    }
+    save(&annotations, Msg::TagAdded(block_id, tag))
+}
+
+pub fn set_block_note(block_id: i32, note: &str) -> bool {
+    let mut annotations = Annotations::load();
+    annotations.blocks.entry(block_id).or_default().note = Some(note.to_string());
+    save(&annotations, Msg::NoteSaved)
+}
+
+pub fn set_pause_note(pause_id: i32, note: &str) -> bool {
+    let mut annotations = Annotations::load();
+    annotations.pauses.entry(pause_id).or_default().note = Some(note.to_string());
+    save(&annotations, Msg::NoteSaved)
+}
+
+/// Moves the annotations of a restored block to its new ID.
+pub fn move_block(old_id: i32, new_id: i32) {
+    let mut annotations = Annotations::load();
+    if let Some(annotation) = annotations.blocks.remove(&old_id) {
+        annotations.blocks.insert(new_id, annotation);
+        save_quietly(&annotations);
+    }
+}
+
+/// Moves the note of a restored pause to its new ID.
+pub fn move_pause(old_id: i32, new_id: i32) {
+    let mut annotations = Annotations::load();
+    if let Some(annotation) = annotations.pauses.remove(&old_id) {
+        annotations.pauses.insert(new_id, annotation);
+        save_quietly(&annotations);
+    }
+}
+
+fn save_quietly(annotations: &Annotations) {
+    save_json(ANNOTATIONS_FILE, annotations, None);
+}
+
+fn save(annotations: &Annotations, success: Msg) -> bool {
+    save_json(ANNOTATIONS_FILE, annotations, Some(success))
+}
@@ -0,0 +1,39 @@
+use crate::{auth::LoginOptions, i18n::Msg};
+
+/// Options accepted on the command line, with whether they take a value.
+pub const OPTIONS: &[(&str, bool)] = &[("--password-stdin", false), ("--password-command", true)];
+
+pub struct Args {
+    pub login: LoginOptions,
+    /// A command to run instead of starting the interactive prompt.
+    pub command: Vec<String>,
+}
+
+pub fn parse_args() -> Result<Args, String> {
+    let mut login = LoginOptions::default();
+    let mut args = std::env::args().skip(1);
+    let mut command = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--password-stdin" => login.password_stdin = true,
+            "--password-command" => match args.next() {
+                Some(command) => login.password_command = Some(command),
+                None => return Err(Msg::MissingPasswordCommand.to_string()),
+            },
+            _ => {
+                if let Some(command) = arg.strip_prefix("--password-command=") {
+                    login.password_command = Some(command.to_string());
+                } else if arg.starts_with("--") {
+                    return Err(Msg::UnknownArgument(&arg).to_string());
+                } else {
+                    command.push(arg);
+                    command.extend(args);
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(Args { login, command })
+}
@@ -1,11 +1,17 @@
-use crate::error::AuthError;
+use crate::{
+    data::data_file_path,
+    error::AuthError,
+    prompt::{is_interactive, prompt_email, prompt_password},
+};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::{io::stdin, process::Command, time::Duration};
 
 const TOKEN_DURATION: Duration = Duration::from_secs(10 * 60);
 const DATA_FILE: &str = "token.json";
-const DATA_DIR: &str = ".work_hours_cli_data";
 const LOGIN_URL: &str = "http://localhost:8080/login";
+const EMAIL_VAR: &str = "WORK_HOURS_EMAIL";
+const PASSWORD_VAR: &str = "WORK_HOURS_PASSWORD";
+const PASSWORD_COMMAND_VAR: &str = "WORK_HOURS_PASSWORD_COMMAND";
 
 #[derive(Serialize, Deserialize)]
 pub struct Token {
@@ -19,6 +25,26 @@ struct Login {
     password: String,
 }
 
+/// Non-interactive credential sources, set via command line flags.
+#[derive(Default)]
+pub struct LoginOptions {
+    pub password_stdin: bool,
+    pub password_command: Option<String>,
+    /// The password taken from stdin before the commands.
+    password: Option<String>,
+}
+
+impl LoginOptions {
+    /// Reads the password from the first line of stdin right away, when the
+    /// rest of stdin holds the commands of a batch.
+    pub fn read_password_ahead(&mut self) -> Result<(), AuthError> {
+        if self.password_stdin && self.password.is_none() {
+            self.password = Some(read_password_from_stdin()?);
+        }
+        Ok(())
+    }
+}
+
 pub struct Authorizer {
     token: Option<Token>,
     pub token_path: String,
@@ -26,30 +52,27 @@ pub struct Authorizer {
 
 impl Authorizer {
     pub fn new() -> Authorizer {
-        let token_path = create_data_dir().expect("Fehler beim Erstellen des Datenordners");
+        let token_path = data_file_path(DATA_FILE).expect("Fehler beim Erstellen des Datenordners");
 
-        let token: Option<Token> = match load_token_from_file(&token_path) {
-            Ok(token) => Some(token),
-            _ => None,
-        };
+        let token: Option<Token> = load_token_from_file(&token_path).ok();
 
         Authorizer { token, token_path }
     }
 
     pub fn token(&self) -> Option<&Token> {
         if let Some(ref token) = self.token {
-            return Some(token);
+            Some(token)
         } else {
-            return None;
+            None
         }
     }
 
-    pub fn login(&mut self, email: String, password: String) -> Result<(), AuthError> {
+    pub fn login(&mut self, options: &LoginOptions) -> Result<(), AuthError> {
         if !self.login_necessary() {
             return Ok(());
         }
 
-        let login = Login { email, password };
+        let login = resolve_credentials(options)?;
         let login = serde_json::to_string(&login)?;
 
         let client = reqwest::blocking::Client::new();
@@ -61,7 +84,7 @@ impl Authorizer {
         }
 
         let token = res.text()?;
-        let token = Token::new(String::from(token));
+        let token = Token::new(token);
 
         save_token_to_file(&token, &self.token_path)?;
         self.token = Some(token);
@@ -71,11 +94,7 @@ impl Authorizer {
 
     pub fn login_necessary(&self) -> bool {
         if let Some(ref token) = self.token {
-            if token.has_expired() {
-                return true;
-            } else {
-                return false;
-            }
+            token.has_expired()
         } else {
             true
         }
@@ -90,11 +109,7 @@ impl Token {
 
     fn has_expired(&self) -> bool {
         let now = std::time::SystemTime::now();
-        if let Ok(_) = self.expires_at.duration_since(now) {
-            false
-        } else {
-            true
-        }
+        self.expires_at.duration_since(now).is_err()
     }
 
     pub fn token_string(&self) -> &str {
@@ -102,6 +117,56 @@ impl Token {
     }
 }
 
+/// Collects email and password from the environment, stdin or the password
+/// command. Missing values are only prompted for if a terminal is attached.
+fn resolve_credentials(options: &LoginOptions) -> Result<Login, AuthError> {
+    let email = std::env::var(EMAIL_VAR).ok();
+
+    let password = if let Some(ref password) = options.password {
+        Some(password.clone())
+    } else if options.password_stdin {
+        Some(read_password_from_stdin()?)
+    } else if let Ok(password) = std::env::var(PASSWORD_VAR) {
+        Some(password)
+    } else if let Some(command) = options
+        .password_command
+        .clone()
+        .or_else(|| std::env::var(PASSWORD_COMMAND_VAR).ok())
+    {
+        Some(run_password_command(&command)?)
+    } else {
+        None
+    };
+
+    if !is_interactive() && (email.is_none() || password.is_none()) {
+        return Err(AuthError::MissingCredentials);
+    }
+
+    let email = email.unwrap_or_else(prompt_email);
+    let password = password.unwrap_or_else(prompt_password);
+
+    Ok(Login { email, password })
+}
+
+fn read_password_from_stdin() -> Result<String, AuthError> {
+    let mut password = String::new();
+    stdin().read_line(&mut password)?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn run_password_command(command: &str) -> Result<String, AuthError> {
+    let output = Command::new("sh").arg("-c").arg(command).output()?;
+
+    if !output.status.success() {
+        return Err(AuthError::PasswordCommandError(output.status.code()));
+    }
+
+    let output = String::from_utf8(output.stdout)?;
+    let password = output.lines().next().unwrap_or_default();
+
+    Ok(password.to_string())
+}
+
 fn save_token_to_file(token: &Token, path: &str) -> Result<(), std::io::Error> {
     let token = serde_json::to_string(&token)?;
     std::fs::write(path, token)?;
@@ -114,22 +179,6 @@ fn load_token_from_file(path: &str) -> Result<Token, AuthError> {
     Ok(token)
 }
 
-fn create_data_dir() -> Result<String, std::io::Error> {
-    let home_dir = home::home_dir().expect("Fehler beim Ermitteln des 'home'-Ordners");
-    let home_dir = home_dir
-        .into_os_string()
-        .into_string()
-        .expect("Fehler beim Ermitteln des 'home'-Ordners");
-
-    if let Err(err) = std::fs::create_dir_all(format!("{home_dir}/{DATA_DIR}")) {
-        return Err(err);
-    }
-
-    let token_path = format!("{home_dir}/{DATA_DIR}/{DATA_FILE}");
-
-    Ok(token_path)
-}
-
 // #[test]
 // fn token_test() {
 //     let jwt = String::from("test_token_content");
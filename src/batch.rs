@@ -0,0 +1,84 @@
+use std::io::{stdin, BufRead, BufReader};
+
+use crate::{
+    i18n::Msg,
+    prompt::{error_text, success_text},
+};
+
+pub struct BatchOptions {
+    /// The file to read the commands from, stdin if `None`.
+    pub path: Option<String>,
+    pub stop_on_error: bool,
+}
+
+/// Parses the arguments of `run [{datei}] [--stop-on-error]`.
+pub fn parse_batch_args(args: &[String]) -> Result<BatchOptions, String> {
+    let mut options = BatchOptions {
+        path: None,
+        stop_on_error: false,
+    };
+
+    for arg in args {
+        match arg.as_str() {
+            "--stop-on-error" => options.stop_on_error = true,
+            "-" if options.path.is_none() => {}
+            _ if arg.starts_with("--") => return Err(Msg::UnknownOption(arg).to_string()),
+            _ if options.path.is_none() => options.path = Some(arg.to_string()),
+            _ => return Err(Msg::UnexpectedArgument(arg).to_string()),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Returns the numbered lines that contain a command, skipping empty lines
+/// and `#` comments.
+pub fn read_commands(path: Option<&str>) -> Result<Vec<(usize, String)>, std::io::Error> {
+    let reader: Box<dyn BufRead> = match path {
+        Some(path) => Box::new(BufReader::new(std::fs::File::open(path)?)),
+        None => Box::new(stdin().lock()),
+    };
+
+    let mut commands = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let command = line.trim();
+        if !command.is_empty() && !command.starts_with('#') {
+            commands.push((i + 1, command.to_string()));
+        }
+    }
+
+    Ok(commands)
+}
+
+#[derive(Default)]
+pub struct BatchSummary {
+    pub succeeded: usize,
+    pub failed_lines: Vec<usize>,
+    pub skipped: usize,
+}
+
+impl BatchSummary {
+    pub fn success(&self) -> bool {
+        self.failed_lines.is_empty()
+    }
+
+    pub fn print(&self) {
+        let total = self.succeeded + self.failed_lines.len();
+        let summary = Msg::BatchSummary(total, self.succeeded, self.failed_lines.len()).to_string();
+
+        if self.success() {
+            println!("{}", success_text(&summary));
+            return;
+        }
+
+        let lines: Vec<String> = self.failed_lines.iter().map(|n| n.to_string()).collect();
+        println!(
+            "{}",
+            error_text(&format!("{summary}{}", Msg::FailedLines(&lines.join(", "))))
+        );
+        if self.skipped > 0 {
+            println!("{}", error_text(&Msg::Skipped(self.skipped).to_string()));
+        }
+    }
+}
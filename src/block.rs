@@ -1,100 +1,213 @@
-use chrono::{DateTime, FixedOffset};
+use chrono::{DateTime, FixedOffset, Timelike, Utc};
 use colored::Colorize;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::time::Duration;
+use crate::{display::DisplayConfig, i18n::Msg, time::Duration};
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Block {
     pub id: i32,
     start: String,
     end: String,
     pauses: Option<Vec<Pause>>,
     homeoffice: bool,
+    /// Kept locally, see `annotations.rs`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
 }
 
 impl Block {
-    pub fn display(&self) {
-        if self.end.len() == 0 {
-            let start = chrono::DateTime::parse_from_rfc3339(&self.start).unwrap();
-            let now = chrono::Local::now();
+    pub fn pauses(&self) -> &[Pause] {
+        self.pauses.as_deref().unwrap_or_default()
+    }
+
+    pub fn pauses_mut(&mut self) -> &mut [Pause] {
+        self.pauses.as_deref_mut().unwrap_or_default()
+    }
+
+    /// Whether the note, project or tags of the block or the notes of its
+    /// pauses contain the text, ignoring case.
+    pub fn matches(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        let contains = |value: &str| value.to_lowercase().contains(&text);
+
+        self.note.as_deref().is_some_and(contains)
+            || self.project.as_deref().is_some_and(contains)
+            || self.tags.iter().any(|tag| contains(tag))
+            || self
+                .pauses()
+                .iter()
+                .any(|pause| pause.note.as_deref().is_some_and(contains))
+    }
+
+    pub fn homeoffice(&self) -> bool {
+        self.homeoffice
+    }
+
+    pub fn active_pause(&self) -> Option<&Pause> {
+        self.pauses()
+            .iter()
+            .find(|pause| pause.end_time().is_none())
+    }
+
+    /// Time since the start of the block, up to `now` while it is active.
+    pub fn gross_time(&self, now: DateTime<Utc>) -> chrono::Duration {
+        let end = self.end_time().map_or(now, |end| end.to_utc());
+        end.signed_duration_since(self.start_time())
+    }
+
+    /// Time spent in pauses, counting an active pause up to `now`.
+    pub fn pause_time(&self, now: DateTime<Utc>) -> chrono::Duration {
+        self.pauses()
+            .iter()
+            .map(|pause| pause.duration(now))
+            .fold(chrono::Duration::zero(), |sum, duration| sum + duration)
+    }
+
+    /// Working time without pauses.
+    pub fn net_time(&self, now: DateTime<Utc>) -> chrono::Duration {
+        self.gross_time(now) - self.pause_time(now)
+    }
+
+    /// The stretches of work between the pauses, up to `now` while the block
+    /// or a pause is active.
+    pub fn working_periods(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)> {
+        let now = now.with_timezone(&self.start_time().timezone());
+        let end = self.end_time().unwrap_or(now);
+
+        let mut pauses: Vec<&Pause> = self.pauses().iter().collect();
+        pauses.sort_by_key(|pause| pause.start_time());
+
+        let mut periods = Vec::new();
+        let mut start = self.start_time();
+        for pause in pauses {
+            if pause.start_time() > start {
+                periods.push((start, pause.start_time().min(end)));
+            }
+            start = start.max(pause.end_time().unwrap_or(now));
+        }
+        if end > start {
+            periods.push((start, end));
+        }
+        periods
+    }
+
+    pub fn start_time(&self) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(&self.start).unwrap()
+    }
+
+    /// `None` while the block is active.
+    pub fn end_time(&self) -> Option<DateTime<FixedOffset>> {
+        (!self.end.is_empty()).then(|| DateTime::parse_from_rfc3339(&self.end).unwrap())
+    }
+
+    pub fn display(&self, display: &DisplayConfig) {
+        if self.end.is_empty() {
+            let start = self.start_time();
+            let now = Utc::now();
 
             let d = Duration::from_chrono_duration(now.signed_duration_since(start));
 
-            println!("Block {} - AKTIV", self.id);
-            println!("Homeoffice: {}", self.homeoffice);
-            println!("Aktiv seit: {}", start.format("%d.%m.%Y %H:%M:%S"));
-            println!("Zeit: {:0>2}:{:0>2}:{:0>2}", d.hours, d.minutes, d.seconds);
+            println!("{}", Msg::BlockActive(self.id));
+            println!("{}", Msg::Homeoffice(self.homeoffice));
+            println!("{}", Msg::ActiveSince(&display.date_time(start)));
+            println!("{}", Msg::Time(&d.to_string()));
         } else {
-            let start = chrono::DateTime::parse_from_rfc3339(&self.start).unwrap();
-            let end = chrono::DateTime::parse_from_rfc3339(&self.end).unwrap();
+            let start = self.start_time();
+            let end = DateTime::parse_from_rfc3339(&self.end).unwrap();
 
             let d = Duration::from_chrono_duration(end.signed_duration_since(start));
 
-            println!("Block {} - ABGESCHLOSSEN", self.id);
-            println!("Homeoffice: {}", self.homeoffice);
-            println!("Start: {}", start.format("%d.%m.%Y %H:%M:%S"));
-            println!("End: {}", end.format("%d.%m.%Y %H:%M:%S"));
-            println!("Zeit: {:0>2}:{:0>2}:{:0>2}", d.hours, d.minutes, d.seconds);
+            println!("{}", Msg::BlockFinished(self.id));
+            println!("{}", Msg::Homeoffice(self.homeoffice));
+            println!("{}", Msg::Start(&display.date_time(start)));
+            println!("{}", Msg::End(&display.date_time(end)));
+            println!("{}", Msg::Time(&d.to_string()));
+        }
+
+        if let Some(ref project) = self.project {
+            println!("{}", Msg::Project(project));
+        }
+        if !self.tags.is_empty() {
+            println!("{}", Msg::Tags(&self.tags.join(", ")));
+        }
+        if let Some(ref note) = self.note {
+            println!("{}", Msg::Note(note));
         }
 
         if let Some(ref pauses) = self.pauses {
             for pause in pauses {
-                pause.display();
+                pause.display(display);
             }
         }
     }
 }
 
-pub fn visualize_blocks(blocks: Vec<Block>) {
-    if blocks.len() == 0 {
+pub fn visualize_blocks(blocks: Vec<Block>, display: &DisplayConfig) {
+    if blocks.is_empty() {
         return;
     }
 
-    let min_start_block = blocks
+    // An active block ends now, in the offset of its start like a stored end.
+    let now = Utc::now();
+    let end_of = |block: &Block| {
+        block
+            .end_time()
+            .unwrap_or_else(|| now.with_timezone(&block.start_time().timezone()))
+    };
+
+    // Blocks are placed by the wall-clock time they started at, so days line
+    // up below each other, while their length is the time elapsed between the
+    // start and end instants, which stays correct across midnight and DST.
+    let offset = |date_time| {
+        display
+            .convert(date_time)
+            .time()
+            .num_seconds_from_midnight() as i64
+            / 60
+    };
+
+    let min_offset = blocks
         .iter()
-        .min_by(|a, b| {
-            DateTime::parse_from_rfc3339(&a.start)
-                .unwrap()
-                .time()
-                .cmp(&DateTime::parse_from_rfc3339(&b.end).unwrap().time())
-        })
+        .map(|block| offset(block.start_time()))
+        .min()
         .unwrap();
-
-    let max_end_block = blocks
+    let max_offset = blocks
         .iter()
-        .max_by(|a, b| {
-            DateTime::parse_from_rfc3339(&a.end)
-                .unwrap()
-                .time()
-                .cmp(&DateTime::parse_from_rfc3339(&b.end).unwrap().time())
+        .map(|block| {
+            offset(block.start_time())
+                + end_of(block)
+                    .signed_duration_since(block.start_time())
+                    .num_minutes()
         })
+        .max()
         .unwrap();
 
-    let min_start = DateTime::parse_from_rfc3339(&min_start_block.start).unwrap();
-    let max_end = DateTime::parse_from_rfc3339(&max_end_block.end).unwrap();
-
-    let total_minutes = max_end
-        .time()
-        .signed_duration_since(min_start.time())
-        .num_minutes();
-    let (w, _) = term_size::dimensions().unwrap();
-    let minutes_per_space = (total_minutes as f64 / w as f64).ceil() as i64;
+    let total_minutes = max_offset - min_offset;
+    let (w, _) = term_size::dimensions().unwrap_or((80, 0));
+    let minutes_per_space = ((total_minutes as f64 / w as f64).ceil() as i64).max(1);
 
     for block in blocks {
-        let start = DateTime::parse_from_rfc3339(&block.start).unwrap();
-        let end = DateTime::parse_from_rfc3339(&block.end).unwrap();
+        let start = block.start_time();
+        let end = end_of(&block);
         let duration = end.signed_duration_since(start);
 
-        let minutes_to_min_start = start
-            .time()
-            .signed_duration_since(min_start.time())
-            .num_minutes();
+        let minutes_to_min_start = offset(start) - min_offset;
 
         let space_to_start = minutes_to_min_start / minutes_per_space;
-        let space_from_start_to_end = duration.num_minutes() / minutes_per_space - 9;
+        let start_label = display.time(start);
+        let end_label = display.time(end);
+        let width = duration.num_minutes() / minutes_per_space + 7;
+        let space_from_start_to_end = width - (start_label.len() + end_label.len()) as i64;
 
-        let block_title = format!("{} - {}", block.id, start.format("%d.%m.%Y"));
+        let block_title = format!("{} - {}", block.id, display.date(start));
 
         match block.pauses {
             Some(pauses) => {
@@ -103,8 +216,8 @@ pub fn visualize_blocks(blocks: Vec<Block>) {
                 points.push(start);
 
                 for pause in pauses {
-                    points.push(DateTime::parse_from_rfc3339(&pause.start).unwrap());
-                    points.push(DateTime::parse_from_rfc3339(&pause.end).unwrap());
+                    points.push(pause.start_time());
+                    points.push(pause.end_time().unwrap_or(now.fixed_offset()));
                 }
 
                 points.push(end);
@@ -116,7 +229,7 @@ pub fn visualize_blocks(blocks: Vec<Block>) {
                     let duration_minutes = duration.num_minutes() / minutes_per_space;
                     let percentage_of_whole: f64 = spaces as f64 / duration_minutes as f64;
 
-                    let extra_spaces = (percentage_of_whole * 8 as f64).floor() as i64;
+                    let extra_spaces = (percentage_of_whole * 8_f64).floor() as i64;
 
                     if i == 1 {
                         print!(
@@ -140,9 +253,9 @@ pub fn visualize_blocks(blocks: Vec<Block>) {
                 println!(
                     "{}{}{}{}",
                     empty_space(space_to_start),
-                    start.format("%H:%M:%S"),
+                    start_label,
                     empty_space(space_from_start_to_end),
-                    end.format("%H:%M:%S")
+                    end_label
                 );
             }
             None => {
@@ -150,15 +263,15 @@ pub fn visualize_blocks(blocks: Vec<Block>) {
                     "{}{}{}",
                     empty_space(space_to_start),
                     block_title.on_green().black(),
-                    empty_space(space_from_start_to_end + 16 - block_title.len() as i64).on_green(),
+                    empty_space(width - block_title.len() as i64).on_green(),
                 );
 
                 println!(
                     "{}{}{}{}",
                     empty_space(space_to_start),
-                    start.format("%H:%M:%S"),
+                    start_label,
                     empty_space(space_from_start_to_end),
-                    end.format("%H:%M:%S")
+                    end_label
                 );
             }
         }
@@ -168,37 +281,197 @@ pub fn visualize_blocks(blocks: Vec<Block>) {
 }
 
 fn empty_space(n: i64) -> String {
-    " ".repeat(n as usize)
+    " ".repeat(n.max(0) as usize)
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Pause {
-    id: i32,
+    pub id: i32,
     start: String,
     end: String,
+    /// Kept locally, see `annotations.rs`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
 }
 
 impl Pause {
-    pub fn display(&self) {
-        if self.end.len() == 0 {
-            let start = chrono::DateTime::parse_from_rfc3339(&self.start).unwrap();
-            let now = chrono::Local::now();
+    pub fn start_time(&self) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(&self.start).unwrap()
+    }
+
+    /// `None` while the pause is active.
+    pub fn end_time(&self) -> Option<DateTime<FixedOffset>> {
+        (!self.end.is_empty()).then(|| DateTime::parse_from_rfc3339(&self.end).unwrap())
+    }
+
+    pub fn duration(&self, now: DateTime<Utc>) -> chrono::Duration {
+        let end = self.end_time().map_or(now, |end| end.to_utc());
+        end.signed_duration_since(self.start_time())
+    }
+
+    pub fn display(&self, display: &DisplayConfig) {
+        if self.end.is_empty() {
+            let start = self.start_time();
+            let now = Utc::now();
 
             let d = Duration::from_chrono_duration(now.signed_duration_since(start));
 
-            println!("Pause {} - AKTIV", self.id);
-            println!("Aktiv seit: {}", start.format("%d.%m.%Y %H:%M:%S"));
-            println!("Zeit: {:0>2}:{:0>2}:{:0>2}", d.hours, d.minutes, d.seconds);
+            println!("{}", Msg::PauseActive(self.id));
+            println!("{}", Msg::ActiveSince(&display.date_time(start)));
+            println!("{}", Msg::Time(&d.to_string()));
         } else {
-            let start = chrono::DateTime::parse_from_rfc3339(&self.start).unwrap();
-            let end = chrono::DateTime::parse_from_rfc3339(&self.end).unwrap();
+            let start = self.start_time();
+            let end = DateTime::parse_from_rfc3339(&self.end).unwrap();
 
             let d = Duration::from_chrono_duration(end.signed_duration_since(start));
 
-            println!("Pause {} - ABGESCHLOSSEN", self.id);
-            println!("Start: {}", start.format("%d.%m.%Y %H:%M:%S"));
-            println!("End: {}", end.format("%d.%m.%Y %H:%M:%S"));
-            println!("Zeit: {:0>2}:{:0>2}:{:0>2}", d.hours, d.minutes, d.seconds);
+            println!("{}", Msg::PauseFinished(self.id));
+            println!("{}", Msg::Start(&display.date_time(start)));
+            println!("{}", Msg::End(&display.date_time(end)));
+            println!("{}", Msg::Time(&d.to_string()));
         }
+
+        if let Some(ref note) = self.note {
+            println!("{}", Msg::Note(note));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(rfc3339: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap()
+    }
+
+    fn block(start: &str, end: &str, pauses: &[(&str, &str)]) -> Block {
+        Block {
+            id: 1,
+            start: start.to_string(),
+            end: end.to_string(),
+            pauses: Some(
+                pauses
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (start, end))| Pause {
+                        id: i as i32 + 1,
+                        start: start.to_string(),
+                        end: end.to_string(),
+                        note: None,
+                    })
+                    .collect(),
+            ),
+            homeoffice: false,
+            project: None,
+            tags: Vec::new(),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn times_across_the_march_switch() {
+        // 02:00 CET becomes 03:00 CEST, 01:00 to 04:00 is two hours.
+        let block = block(
+            "2026-03-29T01:00:00+01:00",
+            "2026-03-29T04:00:00+02:00",
+            &[("2026-03-29T01:30:00+01:00", "2026-03-29T03:30:00+02:00")],
+        );
+        let now = Utc::now();
+
+        assert_eq!(block.gross_time(now), chrono::Duration::hours(2));
+        assert_eq!(block.pause_time(now), chrono::Duration::hours(1));
+        assert_eq!(block.net_time(now), chrono::Duration::hours(1));
+        assert_eq!(
+            block.working_periods(now),
+            [
+                (
+                    time("2026-03-29T01:00:00+01:00"),
+                    time("2026-03-29T01:30:00+01:00")
+                ),
+                (
+                    time("2026-03-29T03:30:00+02:00"),
+                    time("2026-03-29T04:00:00+02:00")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn times_across_the_october_switch() {
+        // 03:00 CEST becomes 02:00 CET, 01:00 to 04:00 is four hours.
+        let block = block(
+            "2026-10-25T01:00:00+02:00",
+            "2026-10-25T04:00:00+01:00",
+            &[("2026-10-25T02:30:00+02:00", "2026-10-25T02:30:00+01:00")],
+        );
+        let now = Utc::now();
+
+        assert_eq!(block.gross_time(now), chrono::Duration::hours(4));
+        assert_eq!(block.pause_time(now), chrono::Duration::hours(1));
+        assert_eq!(block.net_time(now), chrono::Duration::hours(3));
+        assert_eq!(
+            block.working_periods(now),
+            [
+                (
+                    time("2026-10-25T01:00:00+02:00"),
+                    time("2026-10-25T02:30:00+02:00")
+                ),
+                (
+                    time("2026-10-25T02:30:00+01:00"),
+                    time("2026-10-25T04:00:00+01:00")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn times_across_midnight() {
+        let block = block(
+            "2026-10-19T23:00:00+02:00",
+            "2026-10-20T01:00:00+02:00",
+            &[("2026-10-19T23:45:00+02:00", "2026-10-20T00:15:00+02:00")],
+        );
+        let now = Utc::now();
+
+        assert_eq!(block.gross_time(now), chrono::Duration::hours(2));
+        assert_eq!(block.net_time(now), chrono::Duration::minutes(90));
+        assert_eq!(
+            block.working_periods(now),
+            [
+                (
+                    time("2026-10-19T23:00:00+02:00"),
+                    time("2026-10-19T23:45:00+02:00")
+                ),
+                (
+                    time("2026-10-20T00:15:00+02:00"),
+                    time("2026-10-20T01:00:00+02:00")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn active_block_runs_until_now_in_its_offset() {
+        let block = block(
+            "2026-10-19T23:00:00+02:00",
+            "",
+            &[("2026-10-19T23:30:00+02:00", "")],
+        );
+        let now = time("2026-10-19T23:00:00Z").to_utc();
+
+        assert_eq!(block.gross_time(now), chrono::Duration::hours(2));
+        assert_eq!(block.net_time(now), chrono::Duration::minutes(30));
+        assert_eq!(
+            block.working_periods(now),
+            [(
+                time("2026-10-19T23:00:00+02:00"),
+                time("2026-10-19T23:30:00+02:00")
+            )]
+        );
+        assert_eq!(
+            block.working_periods(now)[0].1.offset(),
+            block.start_time().offset()
+        );
     }
 }
@@ -0,0 +1,152 @@
+use rustyline::{
+    completion::Completer, highlight::Highlighter, hint::Hinter, validate::Validator, Context,
+    Helper,
+};
+
+use crate::{
+    absence::Absences,
+    annotations::Annotations,
+    parse::{positional_words, split_commands, tokenize, topics, Arg, COMMANDS},
+};
+
+/// Tab completion for the command grammar defined in `parse.rs`, using the
+/// IDs of the blocks the `ActionHandler` has cached.
+#[derive(Default)]
+pub struct CommandHelper {
+    block_ids: Vec<i32>,
+    pause_ids: Vec<i32>,
+    aliases: Vec<String>,
+}
+
+impl CommandHelper {
+    pub fn set_ids(&mut self, block_ids: Vec<i32>, pause_ids: Vec<i32>) {
+        self.block_ids = block_ids;
+        self.pause_ids = pause_ids;
+    }
+
+    pub fn set_aliases(&mut self, aliases: Vec<String>) {
+        self.aliases = aliases;
+    }
+
+    fn candidates(&self, tokens: &[&str], partial: &str) -> Vec<String> {
+        let position = tokens.len();
+        let mut candidates: Vec<String> = Vec::new();
+
+        for spec in COMMANDS.iter().filter(|spec| spec.matches_prefix(tokens)) {
+            if partial.starts_with("--") {
+                if spec.matches(tokens) {
+                    candidates.extend(spec.flags.iter().map(|flag| format!("--{}", flag.name)));
+                }
+                continue;
+            }
+
+            let words = spec.words.len();
+            if position < words {
+                candidates.push(spec.words[position].to_string());
+            } else if let Some(arg) = spec.args.get(position - words) {
+                candidates.extend(self.arg_candidates(arg));
+            }
+        }
+
+        if position == 0 {
+            candidates.extend(self.aliases.iter().cloned());
+        }
+
+        candidates.retain(|candidate| candidate.starts_with(partial));
+        candidates.sort();
+        candidates.dedup();
+        candidates
+    }
+
+    fn arg_candidates(&self, arg: &Arg) -> Vec<String> {
+        match arg {
+            Arg::Keyword(keyword) => vec![keyword.to_string()],
+            Arg::BlockId => self.block_ids.iter().map(|id| id.to_string()).collect(),
+            Arg::PauseId => self.pause_ids.iter().map(|id| id.to_string()).collect(),
+            Arg::Bool => vec![String::from("true"), String::from("false")],
+            Arg::Date => vec![chrono::Local::now()
+                .format("%Y-%m-%dT%H:%M:%S%:z")
+                .to_string()],
+            Arg::Project => Annotations::load().projects_and_tags().0,
+            Arg::Tag => Annotations::load().projects_and_tags().1,
+            Arg::Text | Arg::File => Vec::new(),
+            Arg::AbsenceId => Absences::load()
+                .ids()
+                .iter()
+                .map(|id| id.to_string())
+                .collect(),
+            Arg::Days => vec![chrono::Local::now().format("%Y-%m-%d").to_string()],
+            Arg::Year => vec![chrono::Local::now().format("%Y").to_string()],
+            Arg::Month => vec![chrono::Local::now().format("%Y-%m").to_string()],
+            Arg::Topic => topics().iter().map(|topic| topic.to_string()).collect(),
+        }
+    }
+}
+
+impl Completer for CommandHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = &line[..pos];
+        let start = line
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace() || *c == ';')
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        let Ok(words) = tokenize(&line[..start]) else {
+            return Ok((start, Vec::new()));
+        };
+        // Only the last of several `;`-separated commands is completed.
+        let words = split_commands(words).pop().unwrap_or_default();
+
+        Ok((
+            start,
+            self.candidates(&positional_words(&words), &line[start..]),
+        ))
+    }
+}
+
+impl Hinter for CommandHelper {
+    type Hint = String;
+}
+
+impl Highlighter for CommandHelper {}
+
+impl Validator for CommandHelper {}
+
+impl Helper for CommandHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustyline::history::DefaultHistory;
+
+    fn complete(line: &str) -> (usize, Vec<String>) {
+        let history = DefaultHistory::new();
+        CommandHelper::default()
+            .complete(line, line.len(), &Context::new(&history))
+            .unwrap_or_else(|_| panic!("'{line}' should complete"))
+    }
+
+    #[test]
+    fn completes_after_multi_byte_whitespace() {
+        let (start, candidates) = complete("block\u{a0}");
+        assert_eq!(start, 7);
+        assert!(candidates.contains(&String::from("start")));
+        assert_eq!(complete("block\u{a0}st"), (7, vec![String::from("start")]));
+    }
+
+    #[test]
+    fn completes_the_last_command_of_a_chain() {
+        assert_eq!(
+            complete("pause end; block st"),
+            (17, vec![String::from("start")])
+        );
+        assert_eq!(complete("pause end;pa"), (10, vec![String::from("pause")]));
+    }
+}
@@ -0,0 +1,61 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::{
+    data::data_file_path,
+    display::DisplayConfig,
+    earnings::EarningsConfig,
+    hooks::HooksConfig,
+    i18n::{Language, Msg},
+    prompt::error_text,
+    reminder::Reminder,
+    rounding::RoundingConfig,
+    status::StatusConfig,
+    tax::TaxConfig,
+    work::WorkConfig,
+};
+
+const CONFIG_FILE: &str = "config.toml";
+
+/// User settings, read from `config.toml` in the data directory.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct Config {
+    /// Names that are replaced by one or more `;`-separated commands.
+    pub aliases: HashMap<String, String>,
+    /// Language of all output, derived from `LANG` if not set.
+    pub language: Option<Language>,
+    pub display: DisplayConfig,
+    pub status: StatusConfig,
+    pub work: WorkConfig,
+    pub tax: TaxConfig,
+    pub earnings: EarningsConfig,
+    pub rounding: RoundingConfig,
+    pub reminders: Vec<Reminder>,
+    pub hooks: HooksConfig,
+}
+
+impl Config {
+    /// Loads the config file, falling back to the defaults if it is missing
+    /// or invalid.
+    pub fn load() -> Config {
+        let Ok(path) = data_file_path(CONFIG_FILE) else {
+            return Config::default();
+        };
+
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return Config::default();
+        };
+
+        match toml::from_str(&content) {
+            Ok(config) => config,
+            Err(err) => {
+                println!(
+                    "{}",
+                    error_text(&Msg::ConfigError(&path, &err.to_string()).to_string())
+                );
+                Config::default()
+            }
+        }
+    }
+}
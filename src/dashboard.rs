@@ -0,0 +1,329 @@
+use chrono::{DateTime, Datelike, FixedOffset, Utc};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block as Panel, Borders, Paragraph},
+    DefaultTerminal,
+};
+use std::time::Instant;
+
+use crate::{
+    auth::Token,
+    block::Block,
+    config::Config,
+    fetch::{ActionHandler, ResponseHandler},
+    i18n::Msg,
+    prompt::error_text,
+    time::Duration,
+    work::worked_between,
+};
+
+/// How often the screen is redrawn, so the timers keep running.
+const TICK: std::time::Duration = std::time::Duration::from_secs(1);
+/// How often the blocks are fetched again, to pick up changes made elsewhere.
+const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Full-screen view of the current block, the totals of today and this week
+/// and today's timeline.
+struct Dashboard {
+    current_block: Option<Block>,
+    blocks: Vec<Block>,
+    /// Used for the next block started while no block is active.
+    homeoffice: bool,
+    message: String,
+    refreshed_at: Instant,
+}
+
+/// Runs the dashboard until `q` is pressed and returns whether the terminal
+/// could be used.
+pub fn run(token: &Token, config: &Config, action_handler: &mut ActionHandler) -> bool {
+    let mut terminal = match ratatui::try_init() {
+        Ok(terminal) => terminal,
+        Err(err) => {
+            println!("{}", error_text(&format!("> {err}")));
+            return false;
+        }
+    };
+
+    let mut dashboard = Dashboard {
+        current_block: None,
+        blocks: Vec::new(),
+        homeoffice: false,
+        message: String::new(),
+        refreshed_at: Instant::now(),
+    };
+    dashboard.refresh(token, action_handler);
+
+    let result = dashboard.event_loop(&mut terminal, token, config, action_handler);
+    ratatui::restore();
+
+    if let Err(err) = result {
+        println!("{}", error_text(&format!("> {err}")));
+        return false;
+    }
+    true
+}
+
+impl Dashboard {
+    fn event_loop(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        token: &Token,
+        config: &Config,
+        action_handler: &mut ActionHandler,
+    ) -> std::io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame, config))?;
+
+            if event::poll(TICK)? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind != KeyEventKind::Press {
+                        continue;
+                    }
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                        KeyCode::Char('b') => self.toggle_block(token, action_handler),
+                        KeyCode::Char('p') => self.toggle_pause(token, action_handler),
+                        KeyCode::Char('h') => self.toggle_homeoffice(token, action_handler),
+                        KeyCode::Char('r') => self.refresh(token, action_handler),
+                        _ => {}
+                    }
+                }
+            }
+
+            if self.refreshed_at.elapsed() >= REFRESH_INTERVAL {
+                self.refresh(token, action_handler);
+            }
+        }
+    }
+
+    fn refresh(&mut self, token: &Token, action_handler: &mut ActionHandler) {
+        action_handler.clear_cache();
+        self.refreshed_at = Instant::now();
+
+        self.current_block = match action_handler.get_current_block(token) {
+            Ok((block, status)) => status.is_success().then_some(block),
+            Err(_) => None,
+        };
+
+        let (blocks, message) = action_handler
+            .get_all_blocks(token)
+            .response_message(Msg::AllBlocks, Msg::NoBlocks);
+        match blocks {
+            Some(blocks) => self.blocks = blocks,
+            None => self.message = message,
+        }
+
+        if let Some(ref current) = self.current_block {
+            if !self.blocks.iter().any(|block| block.id == current.id) {
+                self.blocks.push(current.clone());
+            }
+            self.homeoffice = current.homeoffice();
+        }
+    }
+
+    fn toggle_block(&mut self, token: &Token, action_handler: &mut ActionHandler) {
+        let (_, message) = match self.current_block {
+            Some(_) => action_handler
+                .end_block(token)
+                .response_message(Msg::BlockEnded, Msg::BlockEndFailed),
+            None => action_handler
+                .start_block(token, self.homeoffice)
+                .response_message(Msg::BlockStarted, Msg::BlockAlreadyActive),
+        };
+        self.refresh(token, action_handler);
+        self.message = message;
+    }
+
+    fn toggle_pause(&mut self, token: &Token, action_handler: &mut ActionHandler) {
+        let in_pause = self
+            .current_block
+            .as_ref()
+            .is_some_and(|block| block.active_pause().is_some());
+
+        let (_, message) = match in_pause {
+            true => action_handler
+                .end_pause(token)
+                .response_message(Msg::PauseEnded, Msg::NoPauseActive),
+            false => action_handler
+                .start_pause(token)
+                .response_message(Msg::PauseStarted, Msg::PauseAlreadyActive),
+        };
+        self.refresh(token, action_handler);
+        self.message = message;
+    }
+
+    /// Toggles the homeoffice flag of the current block, or of the next block
+    /// if none is active.
+    fn toggle_homeoffice(&mut self, token: &Token, action_handler: &mut ActionHandler) {
+        let Some(ref block) = self.current_block else {
+            self.homeoffice = !self.homeoffice;
+            self.message = Msg::Homeoffice(self.homeoffice).to_string();
+            return;
+        };
+
+        let (_, message) = action_handler
+            .update_block_homeoffice(block.id, !block.homeoffice(), token)
+            .response_message(Msg::BlockUpdated, Msg::UpdateFailed);
+        self.refresh(token, action_handler);
+        self.message = message;
+    }
+
+    fn draw(&self, frame: &mut ratatui::Frame, config: &Config) {
+        let [current, totals, timeline, keys] = Layout::vertical([
+            Constraint::Length(7),
+            Constraint::Length(3),
+            Constraint::Min(3),
+            Constraint::Length(4),
+        ])
+        .areas(frame.area());
+
+        let bordered = |title: String| {
+            Panel::default()
+                .borders(Borders::ALL)
+                .title(title.trim_start_matches("> ").to_string())
+        };
+
+        frame.render_widget(
+            Paragraph::new(self.current_lines(config))
+                .block(bordered(Msg::CurrentBlock.to_string())),
+            current,
+        );
+        frame.render_widget(
+            Paragraph::new(self.totals_line(config)).block(bordered(String::new())),
+            totals,
+        );
+        frame.render_widget(
+            Paragraph::new(self.timeline_lines(config, timeline.width.saturating_sub(2)))
+                .block(bordered(Msg::Timeline.to_string())),
+            timeline,
+        );
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(Msg::DashboardKeys.to_string()),
+                Line::from(self.message.trim_start_matches("> ").to_string()),
+            ])
+            .block(bordered(String::new())),
+            keys,
+        );
+    }
+
+    fn current_lines(&self, config: &Config) -> Vec<Line<'static>> {
+        let now = Utc::now();
+
+        let Some(ref block) = self.current_block else {
+            return vec![
+                Line::from(Msg::Idle.to_string()),
+                Line::from(Msg::Homeoffice(self.homeoffice).to_string()),
+            ];
+        };
+
+        let mut lines = vec![
+            Line::styled(
+                Msg::BlockActive(block.id).to_string(),
+                Style::default().fg(Color::Green),
+            ),
+            Line::from(Msg::Homeoffice(block.homeoffice()).to_string()),
+            Line::from(Msg::ActiveSince(&config.display.date_time(block.start_time())).to_string()),
+            Line::from(Msg::Time(&format_duration(block.gross_time(now))).to_string()),
+            Line::from(Msg::NetTime(&format_duration(block.net_time(now))).to_string()),
+        ];
+
+        if let Some(pause) = block.active_pause() {
+            lines.push(Line::styled(
+                format!(
+                    "{}: {}",
+                    Msg::PauseActive(pause.id),
+                    format_duration(pause.duration(now))
+                ),
+                Style::default().fg(Color::Blue),
+            ));
+        }
+
+        lines
+    }
+
+    fn totals_line(&self, config: &Config) -> Line<'static> {
+        let now = Utc::now();
+        let today = config.display.today();
+        let week_start =
+            today - chrono::Duration::days(today.weekday().num_days_from_monday() as i64);
+
+        let total = |from| worked_between(&self.blocks, from, today, &config.display, now);
+
+        Line::from(format!(
+            "{}    {}",
+            Msg::Today(&format_duration(total(today))),
+            Msg::ThisWeek(&format_duration(total(week_start)))
+        ))
+    }
+
+    /// Today's blocks as bars, working time in green and pauses in blue,
+    /// scaled to the width of the screen.
+    fn timeline_lines(&self, config: &Config, width: u16) -> Vec<Line<'static>> {
+        let now = Utc::now().fixed_offset();
+        let today = config.display.today();
+
+        let blocks: Vec<&Block> = self
+            .blocks
+            .iter()
+            .filter(|block| config.display.day(block.start_time()) == today)
+            .collect();
+
+        let Some(first) = blocks.iter().map(|block| block.start_time()).min() else {
+            return Vec::new();
+        };
+        let last = blocks
+            .iter()
+            .map(|block| block.end_time().unwrap_or(now))
+            .max()
+            .unwrap_or(now);
+
+        let total = last.signed_duration_since(first).num_seconds().max(1);
+        let column = |date_time: DateTime<FixedOffset>| {
+            (date_time.signed_duration_since(first).num_seconds() * width as i64 / total) as usize
+        };
+
+        let mut lines = Vec::new();
+        for block in blocks {
+            let mut points = vec![block.start_time()];
+            for pause in block.pauses() {
+                points.push(pause.start_time());
+                points.push(pause.end_time().unwrap_or(now));
+            }
+            points.push(block.end_time().unwrap_or(now));
+
+            let mut spans = vec![Span::raw(" ".repeat(column(points[0])))];
+            for (i, segment) in points.windows(2).enumerate() {
+                let color = match i % 2 {
+                    0 => Color::Green,
+                    _ => Color::Blue,
+                };
+                let length = column(segment[1]).saturating_sub(column(segment[0])).max(1);
+                spans.push(Span::styled(" ".repeat(length), Style::default().bg(color)));
+            }
+            lines.push(Line::from(spans));
+
+            let start = config.display.time(block.start_time());
+            let end = block
+                .end_time()
+                .map(|end| config.display.time(end))
+                .unwrap_or_default();
+            lines.push(Line::from(format!(
+                "{}{} - {}  ({})",
+                " ".repeat(column(points[0])),
+                start,
+                end,
+                block.id
+            )));
+        }
+
+        lines
+    }
+}
+
+fn format_duration(duration: chrono::Duration) -> String {
+    Duration::from_chrono_duration(duration).to_string()
+}
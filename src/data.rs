@@ -0,0 +1,73 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    i18n::Msg,
+    prompt::{error_text, success_text},
+};
+
+const DATA_DIR: &str = ".work_hours_cli_data";
+
+/// Returns the path of a file inside the data directory, creating the
+/// directory if necessary.
+pub fn data_file_path(file_name: &str) -> Result<String, std::io::Error> {
+    let home_dir = home::home_dir().expect("Fehler beim Ermitteln des 'home'-Ordners");
+    let home_dir = home_dir
+        .into_os_string()
+        .into_string()
+        .expect("Fehler beim Ermitteln des 'home'-Ordners");
+
+    std::fs::create_dir_all(format!("{home_dir}/{DATA_DIR}"))?;
+
+    Ok(format!("{home_dir}/{DATA_DIR}/{file_name}"))
+}
+
+/// Loads a JSON file from the data directory. A missing file gives the
+/// default value, an invalid one is reported with `error` and treated the
+/// same.
+pub fn load_json<T>(file_name: &str, error: for<'a> fn(&'a str, &'a str) -> Msg<'a>) -> T
+where
+    T: DeserializeOwned + Default,
+{
+    let Ok(path) = data_file_path(file_name) else {
+        return T::default();
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return T::default();
+    };
+
+    match serde_json::from_str(&content) {
+        Ok(value) => value,
+        Err(err) => {
+            println!(
+                "{}",
+                error_text(&error(&path, &err.to_string()).to_string())
+            );
+            T::default()
+        }
+    }
+}
+
+/// Writes a JSON file to the data directory and reports whether it worked,
+/// printing `success` if one is given.
+pub fn save_json<T: Serialize>(file_name: &str, value: &T, success: Option<Msg>) -> bool {
+    let result = data_file_path(file_name).and_then(|path| {
+        let content = serde_json::to_string_pretty(value)?;
+        std::fs::write(path, content)
+    });
+
+    match result {
+        Ok(()) => {
+            if let Some(success) = success {
+                println!("{}", success_text(&success.to_string()));
+            }
+            true
+        }
+        Err(err) => {
+            println!(
+                "{}",
+                error_text(&Msg::WriteError(&err.to_string()).to_string())
+            );
+            false
+        }
+    }
+}
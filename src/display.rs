@@ -0,0 +1,235 @@
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone as _, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+
+use crate::i18n;
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+pub enum Clock {
+    #[serde(rename = "24h")]
+    TwentyFourHour,
+    #[serde(rename = "12h")]
+    TwelveHour,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(try_from = "String")]
+pub enum TimeZone {
+    /// The offset the server stored with each timestamp.
+    Stored,
+    Local,
+    /// An IANA time zone like `Europe/Berlin`.
+    Named(Tz),
+}
+
+impl TryFrom<String> for TimeZone {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<TimeZone, String> {
+        match name.as_str() {
+            "stored" => Ok(TimeZone::Stored),
+            "local" => Ok(TimeZone::Local),
+            _ => name
+                .parse::<Tz>()
+                .map(TimeZone::Named)
+                .map_err(|err| err.to_string()),
+        }
+    }
+}
+
+/// How dates and times are rendered, the `[display]` section of the config.
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// A strftime format, derived from the language if not set.
+    pub date_format: Option<String>,
+    /// A strftime format, overrides `clock` and `seconds`.
+    pub time_format: Option<String>,
+    pub clock: Clock,
+    pub seconds: bool,
+    pub time_zone: TimeZone,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> DisplayConfig {
+        DisplayConfig {
+            date_format: None,
+            time_format: None,
+            clock: Clock::TwentyFourHour,
+            seconds: true,
+            time_zone: TimeZone::Stored,
+        }
+    }
+}
+
+impl DisplayConfig {
+    fn date_format(&self) -> &str {
+        self.date_format.as_deref().unwrap_or(i18n::date_format())
+    }
+
+    fn time_format(&self) -> &str {
+        if let Some(ref format) = self.time_format {
+            return format;
+        }
+        match (self.clock, self.seconds) {
+            (Clock::TwentyFourHour, true) => "%H:%M:%S",
+            (Clock::TwentyFourHour, false) => "%H:%M",
+            (Clock::TwelveHour, true) => "%I:%M:%S %p",
+            (Clock::TwelveHour, false) => "%I:%M %p",
+        }
+    }
+
+    /// Converts a timestamp into the configured time zone.
+    pub fn convert(&self, date_time: DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+        match self.time_zone {
+            TimeZone::Stored => date_time,
+            TimeZone::Local => date_time.with_timezone(&Local).fixed_offset(),
+            TimeZone::Named(tz) => date_time.with_timezone(&tz).fixed_offset(),
+        }
+    }
+
+    /// The timestamp of a wall-clock time in the configured time zone, for
+    /// the stored zone in `offset`. Times repeated by the switch to winter
+    /// time give the first one, times skipped by the switch to summer time
+    /// the end of the gap.
+    pub fn instant(&self, local: NaiveDateTime, offset: FixedOffset) -> DateTime<FixedOffset> {
+        let mut local = local;
+        loop {
+            let instant = match self.time_zone {
+                TimeZone::Stored => offset.from_local_datetime(&local).earliest(),
+                TimeZone::Local => Local
+                    .from_local_datetime(&local)
+                    .earliest()
+                    .map(|date_time| date_time.fixed_offset()),
+                TimeZone::Named(tz) => tz
+                    .from_local_datetime(&local)
+                    .earliest()
+                    .map(|date_time| date_time.fixed_offset()),
+            };
+            match instant {
+                Some(instant) => return instant,
+                None => local += chrono::Duration::minutes(1),
+            }
+        }
+    }
+
+    /// The calendar day of a timestamp in the configured time zone.
+    pub fn day(&self, date_time: DateTime<FixedOffset>) -> NaiveDate {
+        self.convert(date_time).date_naive()
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        match self.time_zone {
+            TimeZone::Stored | TimeZone::Local => Local::now().date_naive(),
+            TimeZone::Named(tz) => Utc::now().with_timezone(&tz).date_naive(),
+        }
+    }
+
+    pub fn date(&self, date_time: DateTime<FixedOffset>) -> String {
+        self.convert(date_time)
+            .format(self.date_format())
+            .to_string()
+    }
+
+    /// Formats a calendar day, e.g. in reports.
+    pub fn day_date(&self, date: NaiveDate) -> String {
+        date.format(self.date_format()).to_string()
+    }
+
+    pub fn time(&self, date_time: DateTime<FixedOffset>) -> String {
+        self.convert(date_time)
+            .format(self.time_format())
+            .to_string()
+    }
+
+    pub fn date_time(&self, date_time: DateTime<FixedOffset>) -> String {
+        format!("{} {}", self.date(date_time), self.time(date_time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn berlin() -> DisplayConfig {
+        DisplayConfig {
+            time_zone: TimeZone::Named(chrono_tz::Europe::Berlin),
+            ..DisplayConfig::default()
+        }
+    }
+
+    fn time(rfc3339: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap()
+    }
+
+    #[test]
+    fn convert_across_the_march_switch() {
+        let display = berlin();
+
+        let before = display.convert(time("2026-03-29T00:30:00Z"));
+        let after = display.convert(time("2026-03-29T01:30:00Z"));
+
+        assert_eq!(before.to_rfc3339(), "2026-03-29T01:30:00+01:00");
+        assert_eq!(after.to_rfc3339(), "2026-03-29T03:30:00+02:00");
+        assert_eq!(after - before, chrono::Duration::hours(1));
+    }
+
+    #[test]
+    fn convert_across_the_october_switch() {
+        let display = berlin();
+
+        let before = display.convert(time("2026-10-25T00:30:00Z"));
+        let after = display.convert(time("2026-10-25T01:30:00Z"));
+
+        assert_eq!(before.to_rfc3339(), "2026-10-25T02:30:00+02:00");
+        assert_eq!(after.to_rfc3339(), "2026-10-25T02:30:00+01:00");
+        assert_eq!(display.time(before), display.time(after));
+        assert_eq!(after - before, chrono::Duration::hours(1));
+    }
+
+    #[test]
+    fn instant_of_wall_clock_times_around_the_switches() {
+        let display = berlin();
+        let local = |rfc3339: &str| time(rfc3339).naive_local();
+        let offset = FixedOffset::east_opt(0).unwrap();
+
+        let skipped = display.instant(local("2026-03-29T02:30:00Z"), offset);
+        let repeated = display.instant(local("2026-10-25T02:30:00Z"), offset);
+
+        assert_eq!(skipped.to_rfc3339(), "2026-03-29T03:00:00+02:00");
+        assert_eq!(repeated.to_rfc3339(), "2026-10-25T02:30:00+02:00");
+    }
+
+    #[test]
+    fn instant_in_the_stored_offset() {
+        let display = DisplayConfig::default();
+        let stored = time("2026-10-19T23:00:00+02:00");
+
+        assert_eq!(
+            display.instant(stored.naive_local(), *stored.offset()),
+            stored
+        );
+    }
+
+    #[test]
+    fn day_after_midnight_in_the_time_zone() {
+        let display = berlin();
+        let late = time("2026-10-19T22:30:00Z");
+
+        assert_eq!(
+            display.day(late),
+            NaiveDate::from_ymd_opt(2026, 10, 20).unwrap()
+        );
+        assert_eq!(display.time(late), "00:30:00");
+    }
+
+    #[test]
+    fn stored_keeps_the_offset() {
+        let display = DisplayConfig::default();
+        let stored = time("2026-10-19T23:00:00+02:00");
+
+        assert_eq!(display.convert(stored), stored);
+        assert_eq!(display.convert(stored).offset(), stored.offset());
+        assert_eq!(display.time(stored), "23:00:00");
+    }
+}
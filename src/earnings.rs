@@ -0,0 +1,405 @@
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, Utc, Weekday};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::{
+    auth::Token,
+    config::Config,
+    display::DisplayConfig,
+    fetch::{ActionHandler, ResponseHandler},
+    holidays::holiday,
+    i18n::Msg,
+    prompt::error_text,
+    time::{Duration, HoursMinutes},
+};
+
+/// An hourly rate paid from a day on, until the next rate takes effect.
+#[derive(Deserialize)]
+pub struct Rate {
+    pub from: NaiveDate,
+    pub hourly: f64,
+}
+
+/// Pay settings, the `[earnings]` section of the config. Surcharges are
+/// percentages of the hourly rate, the night surcharge adds to the Sunday or
+/// holiday surcharge.
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct EarningsConfig {
+    pub rates: Vec<Rate>,
+    pub night_start: HoursMinutes,
+    pub night_end: HoursMinutes,
+    pub night_surcharge: f64,
+    pub sunday_surcharge: f64,
+    /// Paid on public holidays instead of the Sunday surcharge.
+    pub holiday_surcharge: f64,
+}
+
+impl Default for EarningsConfig {
+    fn default() -> EarningsConfig {
+        EarningsConfig {
+            rates: Vec::new(),
+            night_start: HoursMinutes(chrono::Duration::hours(20)),
+            night_end: HoursMinutes(chrono::Duration::hours(6)),
+            night_surcharge: 0.0,
+            sunday_surcharge: 0.0,
+            holiday_surcharge: 0.0,
+        }
+    }
+}
+
+impl EarningsConfig {
+    fn rate_on(&self, day: NaiveDate) -> Option<f64> {
+        self.rates
+            .iter()
+            .filter(|rate| rate.from <= day)
+            .max_by_key(|rate| rate.from)
+            .map(|rate| rate.hourly)
+    }
+
+    fn is_night(&self, date_time: NaiveDateTime) -> bool {
+        let time = date_time
+            .time()
+            .signed_duration_since(chrono::NaiveTime::MIN);
+        let (start, end) = (self.night_start.0, self.night_end.0);
+        match start > end {
+            true => time >= start || time < end,
+            false => time >= start && time < end,
+        }
+    }
+
+    /// Splits working time at midnight and at the start and end of the night
+    /// in the configured time zone, so that the same surcharges apply to each
+    /// part. The parts are timestamps in that zone, so their length stays
+    /// right across a switch between summer and winter time.
+    fn split(
+        &self,
+        start: DateTime<FixedOffset>,
+        end: DateTime<FixedOffset>,
+        display: &DisplayConfig,
+    ) -> Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)> {
+        let mut parts = Vec::new();
+        let mut from = display.convert(start);
+        let end = display.convert(end);
+
+        while from < end {
+            let midnight = from.date_naive().and_time(chrono::NaiveTime::MIN);
+            let to = [
+                midnight + chrono::Duration::days(1),
+                midnight + self.night_start.0,
+                midnight + self.night_end.0,
+            ]
+            .into_iter()
+            .map(|boundary| display.instant(boundary, *from.offset()))
+            .filter(|boundary| *boundary > from)
+            .min()
+            .unwrap_or(end)
+            .min(end);
+
+            parts.push((from, to));
+            from = to;
+        }
+
+        parts
+    }
+}
+
+/// Working time and pay of a day.
+#[derive(Default)]
+struct Earnings {
+    worked: chrono::Duration,
+    night: chrono::Duration,
+    sunday: chrono::Duration,
+    holiday: chrono::Duration,
+    amount: f64,
+}
+
+impl Earnings {
+    fn add(&mut self, other: &Earnings) {
+        self.worked += other.worked;
+        self.night += other.night;
+        self.sunday += other.sunday;
+        self.holiday += other.holiday;
+        self.amount += other.amount;
+    }
+}
+
+/// Prints the gross pay of each day of a month and the totals, computed from
+/// the working time without pauses.
+pub fn print_earnings(
+    month: Option<(i32, u32)>,
+    exact: bool,
+    token: &Token,
+    config: &Config,
+    action_handler: &mut ActionHandler,
+) -> bool {
+    let today = config.display.today();
+    let (year, month) = month.unwrap_or((today.year(), today.month()));
+    let earnings = &config.earnings;
+
+    if earnings.rates.is_empty() {
+        println!("{}", error_text(&Msg::NoRates.to_string()));
+        return false;
+    }
+
+    let Some(blocks) = action_handler
+        .get_all_blocks(token)
+        .handle_response(Msg::EarningsTitle(year, month), Msg::NoBlocks)
+    else {
+        return false;
+    };
+
+    let mut days: BTreeMap<NaiveDate, Earnings> = BTreeMap::new();
+    // Working time of each block per day, for rounding.
+    let mut parts: BTreeMap<(NaiveDate, i32), chrono::Duration> = BTreeMap::new();
+    let mut unpaid: Vec<NaiveDate> = Vec::new();
+    let now = Utc::now();
+
+    for block in &blocks {
+        for (start, end) in block.working_periods(now) {
+            for (from, to) in earnings.split(start, end, &config.display) {
+                let date = from.date_naive();
+                if date.year() != year || date.month() != month {
+                    continue;
+                }
+                let Some(rate) = earnings.rate_on(date) else {
+                    if !unpaid.contains(&date) {
+                        unpaid.push(date);
+                    }
+                    continue;
+                };
+
+                let duration = to - from;
+                let day = days.entry(date).or_default();
+                let mut surcharge = 0.0;
+
+                if holiday(date, config.work.state).is_some() {
+                    day.holiday += duration;
+                    surcharge += earnings.holiday_surcharge;
+                } else if date.weekday() == Weekday::Sun {
+                    day.sunday += duration;
+                    surcharge += earnings.sunday_surcharge;
+                }
+                if earnings.is_night(from.naive_local()) {
+                    day.night += duration;
+                    surcharge += earnings.night_surcharge;
+                }
+
+                day.worked += duration;
+                day.amount += hours(duration) * rate * (1.0 + surcharge / 100.0);
+                *parts.entry((date, block.id)).or_default() += duration;
+            }
+        }
+    }
+
+    // The pay changes in proportion to the rounded working time.
+    let rounding = config.rounding.unless(exact);
+    for (date, day) in days.iter_mut() {
+        let rounded = rounding.total(
+            parts
+                .range((*date, i32::MIN)..=(*date, i32::MAX))
+                .map(|(_, duration)| *duration),
+        );
+        if day.worked > chrono::Duration::zero() {
+            day.amount *= hours(rounded) / hours(day.worked);
+        }
+        day.worked = rounded;
+    }
+
+    let format = |duration| Duration::from_chrono_duration(duration).hours_minutes();
+    let mut total = Earnings::default();
+
+    for (date, day) in &days {
+        println!(
+            "{} {}  {}  {:>12}",
+            Msg::Weekday(date.weekday()),
+            config.display.day_date(*date),
+            format(day.worked),
+            Msg::Euros(day.amount).to_string()
+        );
+        total.add(day);
+    }
+
+    println!();
+    println!(
+        "{}",
+        Msg::EarningsTotal(
+            &format(total.worked),
+            &format(total.night),
+            &format(total.sunday),
+            &format(total.holiday),
+            &Msg::Euros(total.amount).to_string()
+        )
+    );
+
+    for date in unpaid {
+        println!(
+            "{}",
+            error_text(&Msg::NoRateOn(&config.display.day_date(date)).to_string())
+        );
+    }
+
+    true
+}
+
+fn hours(duration: chrono::Duration) -> f64 {
+    duration.num_seconds() as f64 / 3600.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::TimeZone;
+
+    fn time(rfc3339: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap()
+    }
+
+    fn berlin() -> DisplayConfig {
+        DisplayConfig {
+            time_zone: TimeZone::Named(chrono_tz::Europe::Berlin),
+            ..DisplayConfig::default()
+        }
+    }
+
+    fn night(start: i64, end: i64) -> EarningsConfig {
+        EarningsConfig {
+            night_start: HoursMinutes(chrono::Duration::hours(start)),
+            night_end: HoursMinutes(chrono::Duration::hours(end)),
+            ..EarningsConfig::default()
+        }
+    }
+
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 19)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn rendered(parts: &[(DateTime<FixedOffset>, DateTime<FixedOffset>)]) -> Vec<String> {
+        parts
+            .iter()
+            .map(|(from, to)| format!("{} {}", from.to_rfc3339(), to.to_rfc3339()))
+            .collect()
+    }
+
+    fn length(parts: &[(DateTime<FixedOffset>, DateTime<FixedOffset>)]) -> chrono::Duration {
+        parts.iter().map(|(from, to)| *to - *from).sum()
+    }
+
+    #[test]
+    fn night_around_midnight() {
+        let earnings = night(20, 6);
+
+        assert!(earnings.is_night(at(20, 0)));
+        assert!(earnings.is_night(at(23, 59)));
+        assert!(earnings.is_night(at(0, 0)));
+        assert!(earnings.is_night(at(5, 59)));
+        assert!(!earnings.is_night(at(6, 0)));
+        assert!(!earnings.is_night(at(19, 59)));
+    }
+
+    #[test]
+    fn night_within_a_day() {
+        let earnings = night(0, 6);
+
+        assert!(earnings.is_night(at(0, 0)));
+        assert!(earnings.is_night(at(3, 0)));
+        assert!(!earnings.is_night(at(6, 0)));
+        assert!(!earnings.is_night(at(23, 0)));
+    }
+
+    #[test]
+    fn split_at_night_and_midnight() {
+        let parts = night(20, 6).split(
+            time("2026-10-19T18:00:00+02:00"),
+            time("2026-10-20T08:00:00+02:00"),
+            &berlin(),
+        );
+
+        assert_eq!(
+            rendered(&parts),
+            [
+                "2026-10-19T18:00:00+02:00 2026-10-19T20:00:00+02:00",
+                "2026-10-19T20:00:00+02:00 2026-10-20T00:00:00+02:00",
+                "2026-10-20T00:00:00+02:00 2026-10-20T06:00:00+02:00",
+                "2026-10-20T06:00:00+02:00 2026-10-20T08:00:00+02:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn split_in_the_time_zone() {
+        let parts = night(20, 6).split(
+            time("2026-10-19T16:00:00Z"),
+            time("2026-10-19T19:00:00Z"),
+            &berlin(),
+        );
+
+        assert_eq!(
+            rendered(&parts),
+            [
+                "2026-10-19T18:00:00+02:00 2026-10-19T20:00:00+02:00",
+                "2026-10-19T20:00:00+02:00 2026-10-19T21:00:00+02:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn split_across_the_march_switch() {
+        let parts = night(20, 6).split(
+            time("2026-03-29T01:00:00+01:00"),
+            time("2026-03-29T07:00:00+02:00"),
+            &berlin(),
+        );
+
+        assert_eq!(
+            rendered(&parts),
+            [
+                "2026-03-29T01:00:00+01:00 2026-03-29T06:00:00+02:00",
+                "2026-03-29T06:00:00+02:00 2026-03-29T07:00:00+02:00",
+            ]
+        );
+        assert_eq!(length(&parts[..1]), chrono::Duration::hours(4));
+
+        let parts = night(20, 6).split(
+            time("2026-03-29T01:00:00+01:00"),
+            time("2026-03-29T04:00:00+02:00"),
+            &berlin(),
+        );
+        assert_eq!(length(&parts), chrono::Duration::hours(2));
+    }
+
+    #[test]
+    fn split_across_the_october_switch() {
+        let parts = night(20, 6).split(
+            time("2026-10-25T01:00:00+02:00"),
+            time("2026-10-25T04:00:00+01:00"),
+            &berlin(),
+        );
+
+        assert_eq!(
+            rendered(&parts),
+            ["2026-10-25T01:00:00+02:00 2026-10-25T04:00:00+01:00"]
+        );
+        assert_eq!(length(&parts), chrono::Duration::hours(4));
+    }
+
+    #[test]
+    fn split_at_midnight_before_the_october_switch() {
+        let parts = night(20, 6).split(
+            time("2026-10-24T23:00:00+02:00"),
+            time("2026-10-25T01:00:00+02:00"),
+            &berlin(),
+        );
+
+        assert_eq!(
+            rendered(&parts),
+            [
+                "2026-10-24T23:00:00+02:00 2026-10-25T00:00:00+02:00",
+                "2026-10-25T00:00:00+02:00 2026-10-25T01:00:00+02:00",
+            ]
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use core::fmt;
 use std::string::FromUtf8Error;
 
+use crate::i18n::Msg;
+
 pub enum FetchError {
     JSONError(serde_json::Error),
     HTTPError(reqwest::Error),
@@ -28,12 +30,15 @@ impl From<reqwest::Error> for FetchError {
 }
 
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum AuthError {
     JSONError(serde_json::Error),
     HTTPError(reqwest::Error),
     FSError(std::io::Error),
     UTF8Error(FromUtf8Error),
     AuthError(u16),
+    MissingCredentials,
+    PasswordCommandError(Option<i32>),
 }
 
 impl std::fmt::Display for AuthError {
@@ -44,6 +49,11 @@ impl std::fmt::Display for AuthError {
             AuthError::FSError(err) => write!(f, "File system error: {}", err),
             AuthError::UTF8Error(err) => write!(f, "Byte parsing error: {}", err),
             AuthError::AuthError(status) => write!(f, "Authentification error: {}", status),
+            AuthError::MissingCredentials => write!(f, "{}", Msg::MissingCredentials),
+            AuthError::PasswordCommandError(Some(code)) => {
+                write!(f, "{}", Msg::PasswordCommandFailed(*code))
+            }
+            AuthError::PasswordCommandError(None) => write!(f, "{}", Msg::PasswordCommandKilled),
         }
     }
 }
@@ -71,3 +81,100 @@ impl From<FromUtf8Error> for AuthError {
         AuthError::UTF8Error(error)
     }
 }
+
+pub enum ParseError {
+    UnknownCommand {
+        command: String,
+        suggestion: Option<String>,
+    },
+    UnknownVerb {
+        value: String,
+        allowed: String,
+        suggestion: Option<String>,
+        usage: Vec<String>,
+    },
+    MissingArgument {
+        name: String,
+        usage: Vec<String>,
+    },
+    InvalidId {
+        value: String,
+        usage: Vec<String>,
+    },
+    InvalidBool {
+        value: String,
+        usage: Vec<String>,
+    },
+    TooManyArguments {
+        usage: Vec<String>,
+    },
+    UnknownFlag {
+        flag: String,
+        suggestion: Option<String>,
+        usage: Vec<String>,
+    },
+    MissingFlagValue(String),
+    UnexpectedFlagValue(String),
+    UnterminatedQuote,
+    AliasLoop(String),
+}
+
+impl ParseError {
+    /// Usage lines of the commands the input came closest to.
+    pub fn usage(&self) -> &[String] {
+        match self {
+            ParseError::UnknownVerb { usage, .. }
+            | ParseError::MissingArgument { usage, .. }
+            | ParseError::InvalidId { usage, .. }
+            | ParseError::InvalidBool { usage, .. }
+            | ParseError::TooManyArguments { usage }
+            | ParseError::UnknownFlag { usage, .. } => usage,
+            _ => &[],
+        }
+    }
+
+    fn suggestion(&self) -> Option<&str> {
+        match self {
+            ParseError::UnknownCommand { suggestion, .. }
+            | ParseError::UnknownVerb { suggestion, .. }
+            | ParseError::UnknownFlag { suggestion, .. } => suggestion.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnknownCommand { command, .. } => {
+                write!(f, "{}", Msg::UnknownCommand(command))?
+            }
+            ParseError::UnknownVerb { value, allowed, .. } => {
+                write!(f, "{}", Msg::UnknownValue(value, allowed))?
+            }
+            ParseError::MissingArgument { name, .. } => {
+                write!(f, "{}", Msg::MissingArgument(name))?
+            }
+            ParseError::InvalidId { value, .. } => write!(f, "{}", Msg::InvalidId(value))?,
+            ParseError::InvalidBool { value, .. } => write!(f, "{}", Msg::InvalidBool(value))?,
+            ParseError::TooManyArguments { .. } => write!(f, "{}", Msg::TooManyArguments)?,
+            ParseError::UnknownFlag { flag, .. } => {
+                write!(f, "{}", Msg::UnknownOption(&format!("--{flag}")))?
+            }
+            ParseError::MissingFlagValue(flag) => {
+                write!(f, "{}", Msg::MissingOptionValue(&format!("--{flag}")))?
+            }
+            ParseError::UnexpectedFlagValue(flag) => {
+                write!(f, "{}", Msg::UnexpectedOptionValue(&format!("--{flag}")))?
+            }
+            ParseError::UnterminatedQuote => write!(f, "{}", Msg::UnterminatedQuote)?,
+            ParseError::AliasLoop(alias) => write!(f, "{}", Msg::AliasLoop(alias))?,
+        }
+
+        if let Some(suggestion) = self.suggestion() {
+            write!(f, "{}", Msg::DidYouMean(suggestion))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::{
+    auth::Token,
+    block::Block,
+    config::Config,
+    fetch::{ActionHandler, ResponseHandler},
+    i18n::Msg,
+    prompt::{error_text, success_text},
+    time::Duration,
+};
+
+#[derive(Clone, Copy)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+#[derive(Serialize)]
+struct PauseRow<'a> {
+    id: i32,
+    start: String,
+    end: Option<String>,
+    note: Option<&'a str>,
+}
+
+/// A block as exported, with its times in the display time zone. `net` is
+/// rounded per block and `day_net`, the working time of the whole day, per
+/// day as configured, like in the reports.
+#[derive(Serialize)]
+struct BlockRow<'a> {
+    id: i32,
+    day: NaiveDate,
+    start: String,
+    end: Option<String>,
+    homeoffice: bool,
+    gross: String,
+    pause: String,
+    net: String,
+    day_net: String,
+    project: Option<&'a str>,
+    tags: &'a [String],
+    note: Option<&'a str>,
+    pauses: Vec<PauseRow<'a>>,
+}
+
+/// Writes all blocks with their projects, tags and notes as CSV or JSON to a
+/// file or to stdout.
+pub fn export(
+    format: Format,
+    path: Option<&str>,
+    exact: bool,
+    token: &Token,
+    config: &Config,
+    action_handler: &mut ActionHandler,
+) -> bool {
+    let (blocks, message) = action_handler
+        .get_all_blocks(token)
+        .response_message(Msg::AllBlocks, Msg::NoBlocks);
+    let Some(blocks) = blocks else {
+        println!("{}", error_text(&message));
+        return false;
+    };
+
+    let now = Utc::now();
+    let rounding = config.rounding.unless(exact);
+    let date_time =
+        |date_time: DateTime<FixedOffset>| config.display.convert(date_time).to_rfc3339();
+    let format_duration = |duration| Duration::from_chrono_duration(duration).hours_minutes();
+
+    let mut days: BTreeMap<NaiveDate, Vec<chrono::Duration>> = BTreeMap::new();
+    for block in &blocks {
+        days.entry(config.display.day(block.start_time()))
+            .or_default()
+            .push(block.net_time(now));
+    }
+    let day_net = |day: &NaiveDate| format_duration(rounding.total(days[day].iter().copied()));
+
+    let rows: Vec<BlockRow> = blocks
+        .iter()
+        .map(|block: &Block| BlockRow {
+            id: block.id,
+            day: config.display.day(block.start_time()),
+            start: date_time(block.start_time()),
+            end: block.end_time().map(date_time),
+            homeoffice: block.homeoffice(),
+            gross: format_duration(block.gross_time(now)),
+            pause: format_duration(block.pause_time(now)),
+            net: format_duration(rounding.block(block.net_time(now))),
+            day_net: day_net(&config.display.day(block.start_time())),
+            project: block.project.as_deref(),
+            tags: &block.tags,
+            note: block.note.as_deref(),
+            pauses: block
+                .pauses()
+                .iter()
+                .map(|pause| PauseRow {
+                    id: pause.id,
+                    start: date_time(pause.start_time()),
+                    end: pause.end_time().map(date_time),
+                    note: pause.note.as_deref(),
+                })
+                .collect(),
+        })
+        .collect();
+
+    let content = match format {
+        Format::Csv => csv(&rows),
+        Format::Json => match serde_json::to_string_pretty(&rows) {
+            Ok(json) => json + "\n",
+            Err(err) => {
+                println!("{}", error_text(&format!("> {err}")));
+                return false;
+            }
+        },
+    };
+
+    let Some(path) = path else {
+        print!("{content}");
+        return true;
+    };
+    match std::fs::write(path, content) {
+        Ok(()) => {
+            println!(
+                "{}",
+                success_text(&Msg::Exported(rows.len(), path).to_string())
+            );
+            true
+        }
+        Err(err) => {
+            println!(
+                "{}",
+                error_text(&Msg::WriteError(&err.to_string()).to_string())
+            );
+            false
+        }
+    }
+}
+
+/// One line per block, the notes of the pauses are joined into one column.
+fn csv(rows: &[BlockRow]) -> String {
+    let mut csv = String::from(
+        "id,day,start,end,homeoffice,gross,pause,net,day_net,project,tags,note,pause_notes\n",
+    );
+
+    for row in rows {
+        let pause_notes: Vec<&str> = row.pauses.iter().filter_map(|pause| pause.note).collect();
+        let fields = [
+            row.id.to_string(),
+            row.day.to_string(),
+            row.start.clone(),
+            row.end.clone().unwrap_or_default(),
+            row.homeoffice.to_string(),
+            row.gross.clone(),
+            row.pause.clone(),
+            row.net.clone(),
+            row.day_net.clone(),
+            row.project.unwrap_or_default().to_string(),
+            row.tags.join(";"),
+            row.note.unwrap_or_default().to_string(),
+            pause_notes.join(";"),
+        ];
+        let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Quotes a field if it contains a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
@@ -1,8 +1,13 @@
 use crate::{
+    annotations::Annotations,
     auth::Token,
-    block::Block,
+    block::{Block, Pause},
     error::FetchError,
+    hooks::{self, Event, HooksConfig},
+    i18n::Msg,
+    journal::{self, Change, Item},
     prompt::{error_text, success_text},
+    status,
 };
 use reqwest::{blocking::Client, StatusCode};
 use serde::Serialize;
@@ -27,30 +32,34 @@ struct BodyHomeoffice {
 type ActionHandlerResponse<T> = Result<(T, StatusCode), FetchError>;
 
 pub trait ResponseHandler<T> {
-    fn handle_response(self, success_msg: &str, fail_msg: &str) -> Option<T>;
+    fn handle_response(self, success_msg: Msg, fail_msg: Msg) -> Option<T>;
+    /// Like `handle_response`, but returns the message instead of printing it.
+    fn response_message(self, success_msg: Msg, fail_msg: Msg) -> (Option<T>, String);
 }
 
 impl<T> ResponseHandler<T> for ActionHandlerResponse<T> {
-    fn handle_response(self, success_msg: &str, fail_msg: &str) -> Option<T> {
+    fn handle_response(self, success_msg: Msg, fail_msg: Msg) -> Option<T> {
+        let (value, message) = self.response_message(success_msg, fail_msg);
+        match value {
+            Some(_) => println!("{}", success_text(&message)),
+            None => println!("{}", error_text(&message)),
+        }
+        value
+    }
+
+    fn response_message(self, success_msg: Msg, fail_msg: Msg) -> (Option<T>, String) {
         match self {
             Ok(res) => {
                 if res.1.is_success() {
-                    println!("{}", success_text(success_msg));
-                    return Some(res.0);
+                    (Some(res.0), success_msg.to_string())
                 } else {
-                    println!("{}", error_text(fail_msg));
-                    return None;
+                    (None, fail_msg.to_string())
                 }
             }
-            Err(err) => {
-                match err {
-                    FetchError::JSONError(_) => println!("{}", error_text(fail_msg)),
-                    FetchError::HTTPError(_) => {
-                        println!("{}", error_text("> Netzwerk Fehler"))
-                    }
-                };
-                return None;
-            }
+            Err(err) => match err {
+                FetchError::JSONError(_) => (None, fail_msg.to_string()),
+                FetchError::HTTPError(_) => (None, Msg::NetworkError.to_string()),
+            },
         }
     }
 }
@@ -58,6 +67,7 @@ impl<T> ResponseHandler<T> for ActionHandlerResponse<T> {
 pub struct ActionHandler {
     blocks: Option<Vec<Block>>,
     current_block: Option<Block>,
+    hooks: HooksConfig,
 }
 
 impl ActionHandler {
@@ -65,12 +75,37 @@ impl ActionHandler {
         ActionHandler {
             blocks: None,
             current_block: None,
+            hooks: HooksConfig::default(),
         }
     }
 
-    fn clear_cache(&mut self) {
+    pub fn set_hooks(&mut self, hooks: HooksConfig) {
+        self.hooks = hooks;
+    }
+
+    /// Returns the block and pause IDs of all cached blocks.
+    pub fn cached_ids(&self) -> (Vec<i32>, Vec<i32>) {
+        let blocks = self.blocks.iter().flatten().chain(&self.current_block);
+
+        let mut block_ids = Vec::new();
+        let mut pause_ids = Vec::new();
+        for block in blocks {
+            block_ids.push(block.id);
+            pause_ids.extend(block.pauses().iter().map(|pause| pause.id));
+        }
+
+        block_ids.sort_unstable();
+        block_ids.dedup();
+        pause_ids.sort_unstable();
+        pause_ids.dedup();
+
+        (block_ids, pause_ids)
+    }
+
+    pub fn clear_cache(&mut self) {
         self.blocks = None;
         self.current_block = None;
+        status::clear_cache();
     }
 
     fn toggle_current_item(&mut self, route: &str, token: &Token) -> ActionHandlerResponse<()> {
@@ -86,23 +121,73 @@ impl ActionHandler {
         Ok(((), res.status()))
     }
 
+    /// Toggles the current block or pause and fires the hook of the event
+    /// with the affected block.
+    fn toggle_with_hook(
+        &mut self,
+        route: &str,
+        event: Event,
+        token: &Token,
+    ) -> ActionHandlerResponse<()> {
+        if self.hooks.command(event).is_none() {
+            return self.toggle_current_item(route, token);
+        }
+
+        let before = match event.is_end() {
+            true => self.current_block_if_active(token),
+            false => None,
+        };
+
+        let res = self.toggle_current_item(route, token)?;
+
+        if res.1.is_success() {
+            // The ended block is no longer current, so it is fetched by ID
+            // to pass on its end.
+            let block = match (event.is_end(), before) {
+                (true, Some(before)) => self.block_by_id(before.id, token).or(Some(before)),
+                (true, None) => None,
+                (false, _) => self.current_block_if_active(token),
+            };
+            hooks::fire(&self.hooks, event, block.as_ref());
+        }
+
+        Ok(res)
+    }
+
+    fn block_by_id(&mut self, id: i32, token: &Token) -> Option<Block> {
+        match self.get_all_blocks(token) {
+            Ok((blocks, status)) if status.is_success() => {
+                blocks.into_iter().find(|block| block.id == id)
+            }
+            _ => None,
+        }
+    }
+
+    fn current_block_if_active(&mut self, token: &Token) -> Option<Block> {
+        match self.get_current_block(token) {
+            Ok((block, status)) if status.is_success() => Some(block),
+            _ => None,
+        }
+    }
+
     pub fn start_block(&mut self, token: &Token, homeoffice: bool) -> ActionHandlerResponse<()> {
-        self.toggle_current_item(
+        self.toggle_with_hook(
             &format!("current_block_start?homeoffice={homeoffice}"),
+            Event::BlockStart,
             token,
         )
     }
 
     pub fn end_block(&mut self, token: &Token) -> ActionHandlerResponse<()> {
-        self.toggle_current_item("current_block_end", token)
+        self.toggle_with_hook("current_block_end", Event::BlockEnd, token)
     }
 
     pub fn start_pause(&mut self, token: &Token) -> ActionHandlerResponse<()> {
-        self.toggle_current_item("current_pause_start", token)
+        self.toggle_with_hook("current_pause_start", Event::PauseStart, token)
     }
 
     pub fn end_pause(&mut self, token: &Token) -> ActionHandlerResponse<()> {
-        self.toggle_current_item("current_pause_end", token)
+        self.toggle_with_hook("current_pause_end", Event::PauseEnd, token)
     }
 
     pub fn get_current_block(&mut self, token: &Token) -> ActionHandlerResponse<Block> {
@@ -119,7 +204,8 @@ impl ActionHandler {
 
         let status = res.status();
         let text = res.text()?;
-        let block: Block = serde_json::from_str(&text)?;
+        let mut block: Block = serde_json::from_str(&text)?;
+        Annotations::load().apply(&mut block);
 
         self.current_block = Some(block.clone());
 
@@ -140,16 +226,65 @@ impl ActionHandler {
 
         let status = res.status();
         let text = res.text()?;
-        let blocks: Vec<Block> = serde_json::from_str(&text)?;
+        let mut blocks: Vec<Block> = serde_json::from_str(&text)?;
+        let annotations = Annotations::load();
+        blocks.iter_mut().for_each(|block| annotations.apply(block));
 
         self.blocks = Some(blocks.clone());
 
         Ok((blocks, status))
     }
 
-    fn delete_item(&mut self, route: &str, id: i32, token: &Token) -> ActionHandlerResponse<()> {
+    /// All blocks including the current one, which the server may not list
+    /// among all blocks yet.
+    pub fn get_blocks_with_current(&mut self, token: &Token) -> ActionHandlerResponse<Vec<Block>> {
+        let (mut blocks, status) = self.get_all_blocks(token)?;
+
+        match self.get_current_block(token) {
+            Ok((current, current_status)) if current_status.is_success() => {
+                blocks.retain(|block| block.id != current.id);
+                blocks.push(current);
+                Ok((blocks, StatusCode::OK))
+            }
+            _ => Ok((blocks, status)),
+        }
+    }
+
+    /// The state of a block or pause before it is changed, for the journal.
+    fn before_change(
+        &mut self,
+        item: Item,
+        id: i32,
+        deleted: bool,
+        token: &Token,
+    ) -> Option<Change> {
+        match self.get_blocks_with_current(token) {
+            Ok((blocks, status)) if status.is_success() => {
+                Change::before(item, id, deleted, &blocks)
+            }
+            _ => None,
+        }
+    }
+
+    fn delete_item(&mut self, item: Item, id: i32, token: &Token) -> ActionHandlerResponse<()> {
+        let before = self.before_change(item, id, true, token);
+        let res = self.send_delete(item, id, token)?;
+
+        if res.1.is_success() {
+            record(before);
+        }
+
+        Ok(res)
+    }
+
+    /// Deletes a block or pause without recording it in the journal.
+    fn send_delete(&mut self, item: Item, id: i32, token: &Token) -> ActionHandlerResponse<()> {
         self.clear_cache();
 
+        let route = match item {
+            Item::Block => "block",
+            Item::Pause => "pause",
+        };
         let client = Client::new();
         let url = format!("{SERVER_URL}/{route}/{id}");
         let res = client
@@ -161,14 +296,36 @@ impl ActionHandler {
     }
 
     pub fn delete_block(&mut self, id: i32, token: &Token) -> ActionHandlerResponse<()> {
-        self.delete_item("block", id, token)
+        self.delete_item(Item::Block, id, token)
     }
 
     pub fn delete_pause(&mut self, id: i32, token: &Token) -> ActionHandlerResponse<()> {
-        self.delete_item("pause", id, token)
+        self.delete_item(Item::Pause, id, token)
     }
 
     fn update_item<T>(
+        &mut self,
+        item: Item,
+        route: &str,
+        id: i32,
+        body: T,
+        token: &Token,
+    ) -> ActionHandlerResponse<()>
+    where
+        T: Serialize,
+    {
+        let before = self.before_change(item, id, false, token);
+        let res = self.send_update(route, id, body, token)?;
+
+        if res.1.is_success() {
+            record(before);
+        }
+
+        Ok(res)
+    }
+
+    /// Updates a block or pause without recording it in the journal.
+    fn send_update<T>(
         &mut self,
         route: &str,
         id: i32,
@@ -199,7 +356,7 @@ impl ActionHandler {
         start: &str,
         token: &Token,
     ) -> ActionHandlerResponse<()> {
-        self.update_item("block_start", id, BodyStart { start }, token)
+        self.update_item(Item::Block, "block_start", id, BodyStart { start }, token)
     }
 
     pub fn update_block_end(
@@ -208,7 +365,7 @@ impl ActionHandler {
         end: &str,
         token: &Token,
     ) -> ActionHandlerResponse<()> {
-        self.update_item("block_end", id, BodyEnd { end }, token)
+        self.update_item(Item::Block, "block_end", id, BodyEnd { end }, token)
     }
 
     pub fn update_block_homeoffice(
@@ -217,7 +374,13 @@ impl ActionHandler {
         homeoffice: bool,
         token: &Token,
     ) -> ActionHandlerResponse<()> {
-        self.update_item("block_homeoffice", id, BodyHomeoffice { homeoffice }, token)
+        self.update_item(
+            Item::Block,
+            "block_homeoffice",
+            id,
+            BodyHomeoffice { homeoffice },
+            token,
+        )
     }
 
     pub fn update_pause_start(
@@ -226,7 +389,7 @@ impl ActionHandler {
         start: &str,
         token: &Token,
     ) -> ActionHandlerResponse<()> {
-        self.update_item("pause_start", id, BodyStart { start }, token)
+        self.update_item(Item::Pause, "pause_start", id, BodyStart { start }, token)
     }
 
     pub fn update_pause_end(
@@ -235,6 +398,175 @@ impl ActionHandler {
         end: &str,
         token: &Token,
     ) -> ActionHandlerResponse<()> {
-        self.update_item("pause_end", id, BodyEnd { end }, token)
+        self.update_item(Item::Pause, "pause_end", id, BodyEnd { end }, token)
+    }
+
+    /// Restores the state before a change. The server only creates blocks
+    /// and pauses at the current time, so deleted ones are started and ended
+    /// again and then moved to their old times. The new IDs of the recreated
+    /// blocks and pauses are added to `created` as (item, old ID, new ID) as
+    /// soon as they exist, also if a later step fails.
+    pub fn restore(
+        &mut self,
+        change: &Change,
+        created: &mut Vec<(Item, i32, i32)>,
+        token: &Token,
+    ) -> ActionHandlerResponse<()> {
+        match change {
+            Change::BlockUpdated { block } => {
+                let res = self.restore_times(Item::Block, block.id, &times(block), token)?;
+                if !res.1.is_success() {
+                    return Ok(res);
+                }
+                let body = BodyHomeoffice {
+                    homeoffice: block.homeoffice(),
+                };
+                self.send_update("block_homeoffice", block.id, body, token)
+            }
+            Change::PauseUpdated { pause } => {
+                self.restore_times(Item::Pause, pause.id, &pause_times(pause), token)
+            }
+            Change::BlockDeleted { block } => self.recreate_block(block, created, token),
+            Change::PauseDeleted { pause, .. } => self.recreate_pause(pause, created, token),
+        }
+    }
+
+    /// Deletes blocks and pauses recreated by a failed `restore` without
+    /// recording it in the journal. Pauses of a recreated block are deleted
+    /// with it.
+    pub fn discard(
+        &mut self,
+        created: &[(Item, i32, i32)],
+        token: &Token,
+    ) -> ActionHandlerResponse<()> {
+        let block = created.iter().find(|(item, ..)| *item == Item::Block);
+        let items = match block {
+            Some(block) => std::slice::from_ref(block),
+            None => created,
+        };
+        for (item, _, id) in items {
+            let res = self.send_delete(*item, *id, token)?;
+            if !res.1.is_success() {
+                return Ok(res);
+            }
+        }
+        Ok(((), StatusCode::OK))
     }
+
+    /// Sets the start and, if there is one, the end of a block or pause.
+    fn restore_times(
+        &mut self,
+        item: Item,
+        id: i32,
+        (start, end): &(String, Option<String>),
+        token: &Token,
+    ) -> ActionHandlerResponse<()> {
+        let route = match item {
+            Item::Block => ("block_start", "block_end"),
+            Item::Pause => ("pause_start", "pause_end"),
+        };
+        let res = self.send_update(route.0, id, BodyStart { start }, token)?;
+        match end {
+            Some(end) if res.1.is_success() => {
+                self.send_update(route.1, id, BodyEnd { end }, token)
+            }
+            _ => Ok(res),
+        }
+    }
+
+    fn recreate_block(
+        &mut self,
+        block: &Block,
+        created: &mut Vec<(Item, i32, i32)>,
+        token: &Token,
+    ) -> ActionHandlerResponse<()> {
+        let route = format!("current_block_start?homeoffice={}", block.homeoffice());
+        let res = self.toggle_current_item(&route, token)?;
+        if !res.1.is_success() {
+            return Ok(res);
+        }
+        let (current, status) = self.get_current_block(token)?;
+        if !status.is_success() {
+            return Ok(((), status));
+        }
+        created.push((Item::Block, block.id, current.id));
+
+        let (start, end) = times(block);
+        let res = self.restore_times(Item::Block, current.id, &(start, None), token)?;
+        if !res.1.is_success() {
+            return Ok(res);
+        }
+
+        for pause in block.pauses() {
+            let res = self.recreate_pause(pause, created, token)?;
+            if !res.1.is_success() {
+                return Ok(res);
+            }
+        }
+
+        let Some(end) = end else {
+            return Ok(((), StatusCode::OK));
+        };
+        let res = self.toggle_current_item("current_block_end", token)?;
+        if !res.1.is_success() {
+            return Ok(res);
+        }
+        self.send_update("block_end", current.id, BodyEnd { end: &end }, token)
+    }
+
+    /// Recreates a pause in the current block.
+    fn recreate_pause(
+        &mut self,
+        pause: &Pause,
+        created: &mut Vec<(Item, i32, i32)>,
+        token: &Token,
+    ) -> ActionHandlerResponse<()> {
+        let res = self.toggle_current_item("current_pause_start", token)?;
+        if !res.1.is_success() {
+            return Ok(res);
+        }
+        let (current, _) = self.get_current_block(token)?;
+        let Some(new_id) = current.active_pause().map(|pause| pause.id) else {
+            return Ok(((), StatusCode::NOT_FOUND));
+        };
+        created.push((Item::Pause, pause.id, new_id));
+
+        let (start, end) = pause_times(pause);
+        let res = self.restore_times(Item::Pause, new_id, &(start, None), token)?;
+        if !res.1.is_success() {
+            return Ok(res);
+        }
+
+        let Some(end) = end else {
+            return Ok(((), StatusCode::OK));
+        };
+        let res = self.toggle_current_item("current_pause_end", token)?;
+        if !res.1.is_success() {
+            return Ok(res);
+        }
+        self.send_update("pause_end", new_id, BodyEnd { end: &end }, token)
+    }
+}
+
+/// Records the state before a successful change, or warns that it cannot be
+/// undone if the state is unknown.
+fn record(before: Option<Change>) {
+    match before {
+        Some(change) => journal::record(change),
+        None => println!("{}", error_text(&Msg::NotJournaled.to_string())),
+    }
+}
+
+fn times(block: &Block) -> (String, Option<String>) {
+    (
+        block.start_time().to_rfc3339(),
+        block.end_time().map(|end| end.to_rfc3339()),
+    )
+}
+
+fn pause_times(pause: &Pause) -> (String, Option<String>) {
+    (
+        pause.start_time().to_rfc3339(),
+        pause.end_time().map(|end| end.to_rfc3339()),
+    )
 }
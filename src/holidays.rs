@@ -0,0 +1,190 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::Deserialize;
+
+/// The German federal states, by their official abbreviation.
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+pub enum State {
+    BW,
+    BY,
+    BE,
+    BB,
+    HB,
+    HH,
+    HE,
+    MV,
+    NI,
+    NW,
+    RP,
+    SL,
+    SN,
+    ST,
+    SH,
+    TH,
+}
+
+use State::*;
+
+/// Returns the name of the public holiday on the date, nationwide holidays
+/// only if no state is configured.
+pub fn holiday(date: NaiveDate, state: Option<State>) -> Option<&'static str> {
+    holidays(date.year(), state)
+        .into_iter()
+        .find(|(day, _)| *day == date)
+        .map(|(_, name)| name)
+}
+
+/// All public holidays of a year in a state, sorted by date.
+pub fn holidays(year: i32, state: Option<State>) -> Vec<(NaiveDate, &'static str)> {
+    let date = |month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    let easter = easter_sunday(year);
+    let in_states = |states: &[State]| state.is_some_and(|state| states.contains(&state));
+
+    let mut holidays = vec![
+        (date(1, 1), "Neujahr"),
+        (easter - Duration::days(2), "Karfreitag"),
+        (easter + Duration::days(1), "Ostermontag"),
+        (date(5, 1), "Tag der Arbeit"),
+        (easter + Duration::days(39), "Christi Himmelfahrt"),
+        (easter + Duration::days(50), "Pfingstmontag"),
+        (date(10, 3), "Tag der Deutschen Einheit"),
+        (date(12, 25), "1. Weihnachtstag"),
+        (date(12, 26), "2. Weihnachtstag"),
+    ];
+
+    if in_states(&[BW, BY, ST]) {
+        holidays.push((date(1, 6), "Heilige Drei Könige"));
+    }
+    if (in_states(&[BE]) && year >= 2019) || (in_states(&[MV]) && year >= 2023) {
+        holidays.push((date(3, 8), "Internationaler Frauentag"));
+    }
+    if in_states(&[BB]) {
+        holidays.push((easter, "Ostersonntag"));
+        holidays.push((easter + Duration::days(49), "Pfingstsonntag"));
+    }
+    if in_states(&[BW, BY, HE, NW, RP, SL]) {
+        holidays.push((easter + Duration::days(60), "Fronleichnam"));
+    }
+    if in_states(&[SL]) {
+        holidays.push((date(8, 15), "Mariä Himmelfahrt"));
+    }
+    if in_states(&[TH]) && year >= 2019 {
+        holidays.push((date(9, 20), "Weltkindertag"));
+    }
+    if in_states(&[BB, MV, SN, ST, TH])
+        || (in_states(&[HB, HH, NI, SH]) && year >= 2018)
+        || year == 2017
+    {
+        holidays.push((date(10, 31), "Reformationstag"));
+    }
+    if in_states(&[BW, BY, NW, RP, SL]) {
+        holidays.push((date(11, 1), "Allerheiligen"));
+    }
+    if in_states(&[SN]) {
+        holidays.push((repentance_day(year), "Buß- und Bettag"));
+    }
+
+    holidays.sort();
+    holidays
+}
+
+/// Easter Sunday by the anonymous Gregorian algorithm.
+fn easter_sunday(year: i32) -> NaiveDate {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap()
+}
+
+/// The Wednesday before 23 November.
+fn repentance_day(year: i32) -> NaiveDate {
+    let mut date = NaiveDate::from_ymd_opt(year, 11, 22).unwrap();
+    while date.weekday() != Weekday::Wed {
+        date = date.pred_opt().unwrap();
+    }
+    date
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn easter_sundays() {
+        assert_eq!(easter_sunday(2024), date(2024, 3, 31));
+        assert_eq!(easter_sunday(2025), date(2025, 4, 20));
+        assert_eq!(easter_sunday(2026), date(2026, 4, 5));
+    }
+
+    #[test]
+    fn holidays_relative_to_easter() {
+        assert_eq!(holiday(date(2026, 4, 3), None), Some("Karfreitag"));
+        assert_eq!(holiday(date(2026, 4, 6), None), Some("Ostermontag"));
+        assert_eq!(
+            holiday(date(2026, 5, 14), None),
+            Some("Christi Himmelfahrt")
+        );
+        assert_eq!(holiday(date(2026, 5, 25), None), Some("Pfingstmontag"));
+        assert_eq!(holiday(date(2026, 4, 5), None), None);
+        assert_eq!(holiday(date(2026, 4, 5), Some(BB)), Some("Ostersonntag"));
+    }
+
+    #[test]
+    fn repentance_day_in_saxony() {
+        assert_eq!(repentance_day(2026), date(2026, 11, 18));
+        assert_eq!(repentance_day(2023), date(2023, 11, 22));
+        assert_eq!(
+            holiday(date(2026, 11, 18), Some(SN)),
+            Some("Buß- und Bettag")
+        );
+        assert_eq!(holiday(date(2026, 11, 18), Some(BY)), None);
+    }
+
+    #[test]
+    fn corpus_christi_by_state() {
+        assert_eq!(holiday(date(2026, 6, 4), Some(BY)), Some("Fronleichnam"));
+        assert_eq!(holiday(date(2026, 6, 4), Some(BE)), None);
+        assert_eq!(holiday(date(2026, 6, 4), None), None);
+    }
+
+    #[test]
+    fn reformation_day() {
+        for state in [None, Some(BY), Some(BE), Some(NW)] {
+            assert_eq!(holiday(date(2017, 10, 31), state), Some("Reformationstag"));
+        }
+        assert_eq!(holiday(date(2016, 10, 31), Some(HH)), None);
+        assert_eq!(
+            holiday(date(2018, 10, 31), Some(HH)),
+            Some("Reformationstag")
+        );
+        assert_eq!(holiday(date(2026, 10, 31), Some(BY)), None);
+    }
+
+    #[test]
+    fn holidays_are_sorted() {
+        let dates: Vec<NaiveDate> = holidays(2026, Some(BY))
+            .into_iter()
+            .map(|(date, _)| date)
+            .collect();
+        let mut sorted = dates.clone();
+        sorted.sort();
+
+        assert_eq!(dates, sorted);
+        assert_eq!(dates.len(), 12);
+    }
+}
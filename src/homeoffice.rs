@@ -0,0 +1,211 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use std::collections::BTreeMap;
+
+use crate::{
+    auth::Token,
+    block::Block,
+    config::Config,
+    display::DisplayConfig,
+    fetch::{ActionHandler, ResponseHandler},
+    i18n::Msg,
+    prompt::{error_text, success_text},
+    rounding::RoundingConfig,
+    time::Duration,
+};
+
+/// Where the work of a day was done. A day with any block in the office
+/// counts as an office day.
+#[derive(Default)]
+pub struct PlaceDay {
+    pub in_office: bool,
+    pub office_time: chrono::Duration,
+    pub homeoffice_time: chrono::Duration,
+}
+
+/// Office and homeoffice days and hours of a period.
+#[derive(Default)]
+struct Share {
+    office_days: u32,
+    homeoffice_days: u32,
+    office_time: chrono::Duration,
+    homeoffice_time: chrono::Duration,
+}
+
+impl Share {
+    fn add(&mut self, day: &PlaceDay) {
+        match day.in_office {
+            true => self.office_days += 1,
+            false => self.homeoffice_days += 1,
+        }
+        self.office_time += day.office_time;
+        self.homeoffice_time += day.homeoffice_time;
+    }
+
+    /// Percentage of homeoffice days among all days worked.
+    fn percent(&self) -> f64 {
+        let days = self.office_days + self.homeoffice_days;
+        match days {
+            0 => 0.0,
+            _ => self.homeoffice_days as f64 * 100.0 / days as f64,
+        }
+    }
+
+    fn line(&self, label: &str) -> String {
+        let format = |duration| Duration::from_chrono_duration(duration).hours_minutes();
+        format!(
+            "{label:<10}{}  {:.0} %",
+            Msg::PlaceShare(
+                &Msg::DayCount(self.office_days).to_string(),
+                &format(self.office_time),
+                &Msg::DayCount(self.homeoffice_days).to_string(),
+                &format(self.homeoffice_time)
+            ),
+            self.percent()
+        )
+    }
+}
+
+/// The days between `from` and `to`, both inclusive, on which blocks were
+/// started, with the place of work and the rounded working times.
+pub fn place_days(
+    blocks: &[Block],
+    from: NaiveDate,
+    to: NaiveDate,
+    display: &DisplayConfig,
+    rounding: &RoundingConfig,
+    now: DateTime<Utc>,
+) -> BTreeMap<NaiveDate, PlaceDay> {
+    let mut days: BTreeMap<NaiveDate, PlaceDay> = BTreeMap::new();
+
+    for block in blocks {
+        let day = display.day(block.start_time());
+        if !(from..=to).contains(&day) {
+            continue;
+        }
+
+        let place = days.entry(day).or_default();
+        let time = rounding.block(block.net_time(now));
+        match block.homeoffice() {
+            true => place.homeoffice_time += time,
+            false => {
+                place.in_office = true;
+                place.office_time += time;
+            }
+        }
+    }
+
+    for place in days.values_mut() {
+        place.office_time = rounding.day(place.office_time);
+        place.homeoffice_time = rounding.day(place.homeoffice_time);
+    }
+
+    days
+}
+
+fn month_range(year: i32, month: u32) -> Option<(NaiveDate, NaiveDate)> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let last = first
+        .iter_days()
+        .take_while(|day| day.month() == month)
+        .last()?;
+    Some((first, last))
+}
+
+/// Prints the office and homeoffice days and hours of each week of a month
+/// and of the whole month, compared with the quota.
+pub fn print_homeoffice(
+    month: Option<(i32, u32)>,
+    exact: bool,
+    token: &Token,
+    config: &Config,
+    action_handler: &mut ActionHandler,
+) -> bool {
+    let today = config.display.today();
+    let (year, month) = month.unwrap_or((today.year(), today.month()));
+    let Some((first, last)) = month_range(year, month) else {
+        return false;
+    };
+
+    let Some(blocks) = action_handler
+        .get_all_blocks(token)
+        .handle_response(Msg::HomeofficeTitle(year, month), Msg::NoBlocks)
+    else {
+        return false;
+    };
+
+    let days = place_days(
+        &blocks,
+        first,
+        last,
+        &config.display,
+        &config.rounding.unless(exact),
+        Utc::now(),
+    );
+
+    let mut weeks: BTreeMap<(i32, u32), Share> = BTreeMap::new();
+    let mut total = Share::default();
+    for (day, place) in &days {
+        let week = day.iso_week();
+        weeks
+            .entry((week.year(), week.week()))
+            .or_default()
+            .add(place);
+        total.add(place);
+    }
+
+    for ((_, week), share) in &weeks {
+        println!("{}", share.line(&Msg::Week(*week).to_string()));
+    }
+    println!();
+    println!("{}", total.line(&Msg::Month.to_string()));
+
+    if let Some(quota) = config.work.homeoffice_quota {
+        let percent = total.percent();
+        let message = Msg::HomeofficeQuota(percent, quota).to_string();
+        match percent > quota as f64 {
+            true => println!("{}", error_text(&message)),
+            false => println!("{}", success_text(&message)),
+        }
+    }
+
+    true
+}
+
+/// Warns if working in homeoffice today would bring this month's share of
+/// homeoffice days above the quota.
+pub fn warn_about_quota(token: &Token, config: &Config, action_handler: &mut ActionHandler) {
+    let Some(quota) = config.work.homeoffice_quota else {
+        return;
+    };
+    let Ok((blocks, status)) = action_handler.get_all_blocks(token) else {
+        return;
+    };
+    if !status.is_success() {
+        return;
+    }
+
+    let today = config.display.today();
+    let Some((first, last)) = month_range(today.year(), today.month()) else {
+        return;
+    };
+    let mut days = place_days(
+        &blocks,
+        first,
+        last,
+        &config.display,
+        &RoundingConfig::default(),
+        Utc::now(),
+    );
+    days.entry(today).or_default();
+
+    let mut total = Share::default();
+    days.values().for_each(|day| total.add(day));
+
+    let percent = total.percent();
+    if percent > quota as f64 {
+        println!(
+            "{}",
+            error_text(&Msg::HomeofficeQuotaWarning(percent, quota).to_string())
+        );
+    }
+}
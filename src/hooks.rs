@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+    sync::Mutex,
+    thread::JoinHandle,
+};
+use wait_timeout::ChildExt;
+
+use crate::{block::Block, block::Pause, i18n::Msg, prompt::error_text};
+
+/// Hooks still running, waited for before the program exits.
+static RUNNING: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+
+/// Commands run after state changes, the `[hooks]` section of the config.
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct HooksConfig {
+    pub on_block_start: Option<String>,
+    pub on_block_end: Option<String>,
+    pub on_pause_start: Option<String>,
+    pub on_pause_end: Option<String>,
+    pub on_login: Option<String>,
+    /// Hooks running longer are killed.
+    pub timeout_seconds: u64,
+}
+
+impl Default for HooksConfig {
+    fn default() -> HooksConfig {
+        HooksConfig {
+            on_block_start: None,
+            on_block_end: None,
+            on_pause_start: None,
+            on_pause_end: None,
+            on_login: None,
+            timeout_seconds: 10,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Event {
+    BlockStart,
+    BlockEnd,
+    PauseStart,
+    PauseEnd,
+    Login,
+}
+
+impl Event {
+    fn name(&self) -> &'static str {
+        match self {
+            Event::BlockStart => "block_start",
+            Event::BlockEnd => "block_end",
+            Event::PauseStart => "pause_start",
+            Event::PauseEnd => "pause_end",
+            Event::Login => "login",
+        }
+    }
+
+    /// Whether the block has to be read before the change, because it is no
+    /// longer current afterwards.
+    pub fn is_end(&self) -> bool {
+        matches!(self, Event::BlockEnd | Event::PauseEnd)
+    }
+}
+
+impl HooksConfig {
+    pub fn command(&self, event: Event) -> Option<&str> {
+        match event {
+            Event::BlockStart => self.on_block_start.as_deref(),
+            Event::BlockEnd => self.on_block_end.as_deref(),
+            Event::PauseStart => self.on_pause_start.as_deref(),
+            Event::PauseEnd => self.on_pause_end.as_deref(),
+            Event::Login => self.on_login.as_deref(),
+        }
+        .filter(|command| !command.is_empty())
+    }
+}
+
+/// Passed to the hook as JSON on stdin.
+#[derive(Serialize)]
+struct Payload<'a> {
+    event: &'static str,
+    time: String,
+    block: Option<&'a Block>,
+    pause: Option<&'a Pause>,
+}
+
+/// Runs the hook of the event in the background, with the block and its
+/// active or last pause as JSON on stdin and in environment variables.
+pub fn fire(config: &HooksConfig, event: Event, block: Option<&Block>) {
+    let Some(command) = config.command(event) else {
+        return;
+    };
+
+    let pause = block.and_then(|block| match event {
+        Event::PauseStart | Event::PauseEnd => block
+            .active_pause()
+            .or_else(|| block.pauses().iter().max_by_key(|pause| pause.start_time())),
+        _ => None,
+    });
+
+    let payload = Payload {
+        event: event.name(),
+        time: chrono::Local::now().to_rfc3339(),
+        block,
+        pause,
+    };
+    let Ok(json) = serde_json::to_string(&payload) else {
+        return;
+    };
+
+    let mut env = vec![("WORK_HOURS_EVENT", event.name().to_string())];
+    if let Some(block) = block {
+        env.push(("WORK_HOURS_BLOCK_ID", block.id.to_string()));
+        env.push(("WORK_HOURS_BLOCK_START", block.start_time().to_rfc3339()));
+        env.push(("WORK_HOURS_HOMEOFFICE", block.homeoffice().to_string()));
+        if let Some(end) = block.end_time() {
+            env.push(("WORK_HOURS_BLOCK_END", end.to_rfc3339()));
+        }
+    }
+    if let Some(pause) = pause {
+        env.push(("WORK_HOURS_PAUSE_ID", pause.id.to_string()));
+        env.push(("WORK_HOURS_PAUSE_START", pause.start_time().to_rfc3339()));
+        if let Some(end) = pause.end_time() {
+            env.push(("WORK_HOURS_PAUSE_END", end.to_rfc3339()));
+        }
+    }
+
+    let name = format!("on_{}", event.name());
+    let command = command.to_string();
+    let timeout = std::time::Duration::from_secs(config.timeout_seconds);
+
+    let handle = std::thread::spawn(move || {
+        if let Err(message) = run(&command, &json, env, timeout) {
+            eprintln!(
+                "{}",
+                error_text(&Msg::HookFailed(&name, &message).to_string())
+            );
+        }
+    });
+
+    if let Ok(mut running) = RUNNING.lock() {
+        running.retain(|handle| !handle.is_finished());
+        running.push(handle);
+    }
+}
+
+fn run(
+    command: &str,
+    json: &str,
+    env: Vec<(&str, String)>,
+    timeout: std::time::Duration,
+) -> Result<(), String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .envs(env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|err| err.to_string())?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The hook may not read its input at all.
+        let _ = stdin.write_all(json.as_bytes());
+    }
+
+    match child.wait_timeout(timeout).map_err(|err| err.to_string())? {
+        Some(status) if status.success() => Ok(()),
+        Some(status) => Err(Msg::ExitCode(status.code().unwrap_or(-1)).to_string()),
+        None => {
+            let _ = child.kill();
+            let _ = child.wait();
+            Err(Msg::HookTimeout(timeout.as_secs()).to_string())
+        }
+    }
+}
+
+/// Waits for running hooks, so they are not killed when the program exits.
+pub fn wait_for_hooks() {
+    let handles = match RUNNING.lock() {
+        Ok(mut running) => std::mem::take(&mut *running),
+        Err(_) => return,
+    };
+    for handle in handles {
+        let _ = handle.join();
+    }
+}
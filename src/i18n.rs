@@ -0,0 +1,917 @@
+use serde::Deserialize;
+use std::{fmt, sync::OnceLock};
+
+use crate::{absence::Kind, journal::Item};
+
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    De,
+    En,
+}
+
+impl Language {
+    /// Derives the language from `LANG`, German unless another language is
+    /// set explicitly.
+    pub fn from_env() -> Language {
+        match std::env::var("LANG") {
+            Ok(lang) if lang.starts_with("de") || lang.is_empty() => Language::De,
+            Ok(lang) if lang == "C" || lang.starts_with("C.") || lang == "POSIX" => Language::De,
+            Ok(_) => Language::En,
+            Err(_) => Language::De,
+        }
+    }
+}
+
+pub fn set_language(language: Language) {
+    let _ = LANGUAGE.set(language);
+}
+
+pub fn language() -> Language {
+    *LANGUAGE.get_or_init(Language::from_env)
+}
+
+pub fn date_format() -> &'static str {
+    match language() {
+        Language::De => "%d.%m.%Y",
+        Language::En => "%Y-%m-%d",
+    }
+}
+
+/// Writes a number with a decimal comma, as usual in German.
+fn decimal_comma(number: f64) -> String {
+    number.to_string().replace('.', ",")
+}
+
+/// All user-facing texts, rendered in the configured language.
+pub enum Msg<'a> {
+    LoginSuccess,
+    LoginFailed,
+    PromptEmail,
+    PromptPassword,
+    PromptCommand,
+    ConfirmDeleteBlock(i32),
+    ConfirmDeletePause(i32),
+    DeleteCancelled,
+    ProgramExit,
+    NetworkError,
+    BlockStarted,
+    BlockAlreadyActive,
+    BlockEnded,
+    BlockEndFailed,
+    PauseStarted,
+    PauseAlreadyActive,
+    PauseEnded,
+    NoPauseActive,
+    CurrentBlock,
+    NoBlockActive,
+    AllBlocks,
+    NoBlocks,
+    BlockDeleted,
+    BlockNotFound,
+    BlockUpdated,
+    PauseDeleted,
+    PauseNotFound,
+    PauseUpdated,
+    UpdateFailed,
+    BlockActive(i32),
+    BlockFinished(i32),
+    PauseActive(i32),
+    PauseFinished(i32),
+    Homeoffice(bool),
+    ActiveSince(&'a str),
+    Start(&'a str),
+    End(&'a str),
+    Time(&'a str),
+    Idle,
+    NetTime(&'a str),
+    Today(&'a str),
+    ThisWeek(&'a str),
+    Timeline,
+    TargetReachedIn(&'a str),
+    TargetExceededBy(&'a str),
+    ProjectedEnd(&'a str),
+    PressAnyKey,
+    WorkedToday(&'a str, &'a str),
+    TargetAt(&'a str, &'a str),
+    PauseThresholdAt(i64, i64, &'a str),
+    MaximumAt(i64, &'a str),
+    PauseMissing(&'a str),
+    AlreadyReached,
+    ReminderWorked(&'a str),
+    ReminderWithoutPause(&'a str),
+    ReminderTarget(&'a str),
+    RemindersActive(usize),
+    NoReminders,
+    HookFailed(&'a str, &'a str),
+    HookTimeout(u64),
+    ExitCode(i32),
+    DashboardKeys,
+    ReportTitle(i32, u32),
+    ReportTotal(&'a str, &'a str, &'a str, &'a str),
+    Weekday(chrono::Weekday),
+    HomeofficeTitle(i32, u32),
+    TaxTitle(i32),
+    MonthOfYear(i32, u32),
+    TaxDays(&'a str, &'a str),
+    TaxHomeofficeAllowance(&'a str, u32, &'a str),
+    Euros(f64),
+    EarningsTitle(i32, u32),
+    EarningsTotal(&'a str, &'a str, &'a str, &'a str, &'a str),
+    NoRates,
+    NoRateOn(&'a str),
+    Week(u32),
+    Month,
+    DayCount(u32),
+    PlaceShare(&'a str, &'a str, &'a str, &'a str),
+    HomeofficeQuota(f64, u32),
+    HomeofficeQuotaWarning(f64, u32),
+    Aliases,
+    Usage(&'a str),
+    HelpHint,
+    ArgDate,
+    ArgTopic,
+    ArgYear,
+    ArgProject,
+    ArgTag,
+    ArgText,
+    ArgFile,
+    ArgMonth,
+    ArgDays,
+    ArgValue,
+    HelpBlockStart,
+    HelpBlockStartHomeoffice,
+    HelpBlockEnd,
+    HelpBlockDelete,
+    HelpBlockCurrent,
+    HelpBlockAll,
+    HelpBlockUpdateStart,
+    HelpBlockUpdateEnd,
+    HelpBlockUpdateHomeoffice,
+    HelpBlockProject,
+    HelpBlockTag,
+    HelpBlockNote,
+    HelpBlockSearch,
+    HelpPauseNote,
+    HelpPauseStart,
+    HelpPauseEnd,
+    HelpPauseDelete,
+    HelpPauseUpdateStart,
+    HelpPauseUpdateEnd,
+    HelpStatus,
+    HelpDashboard,
+    HelpFeierabend,
+    HelpReport,
+    HelpReportMonth,
+    HelpHomeoffice,
+    HelpHomeofficeMonth,
+    HelpExportCsv,
+    HelpExportCsvFile,
+    HelpExportJson,
+    HelpExportJsonFile,
+    HelpProjects,
+    HelpProjectsMonth,
+    HelpEarnings,
+    HelpEarningsMonth,
+    HelpTax,
+    HelpTaxYear,
+    HelpAbsenceAddVacation,
+    HelpAbsenceAddSick,
+    HelpAbsenceAddComp,
+    HelpAbsenceList,
+    HelpAbsenceDelete,
+    HelpUndo,
+    HelpHelp,
+    HelpHelpTopic,
+    HelpExit,
+    UnknownCommand(&'a str),
+    UnknownValue(&'a str, &'a str),
+    MissingArgument(&'a str),
+    InvalidId(&'a str),
+    InvalidBool(&'a str),
+    TooManyArguments,
+    UnknownOption(&'a str),
+    MissingOptionValue(&'a str),
+    UnexpectedOptionValue(&'a str),
+    UnterminatedQuote,
+    AliasLoop(&'a str),
+    DidYouMean(&'a str),
+    UnknownArgument(&'a str),
+    UnexpectedArgument(&'a str),
+    MissingPasswordCommand,
+    MissingCredentials,
+    PasswordCommandFailed(i32),
+    PasswordCommandKilled,
+    UnknownShell(&'a str, &'a str),
+    ConfigError(&'a str, &'a str),
+    ReadError(&'a str),
+    WriteError(&'a str),
+    AnnotationsError(&'a str, &'a str),
+    ProjectSet(i32, &'a str),
+    TagAdded(i32, &'a str),
+    Project(&'a str),
+    Tags(&'a str),
+    ProjectsTitle(i32, u32),
+    NoProject,
+    TagsTitle,
+    Note(&'a str),
+    NoteSaved,
+    SearchResults(&'a str),
+    NothingFound,
+    Exported(usize, &'a str),
+    JournalError(&'a str, &'a str),
+    NothingToUndo,
+    NotJournaled,
+    UndoFailed,
+    UndoRolledBack,
+    UndoRollbackFailed,
+    UndoBlockActive,
+    UndoPauseOutsideBlock(i32),
+    UndoneBlockDeleted(i32),
+    UndoneBlockUpdated(i32),
+    UndonePauseDeleted(i32),
+    UndonePauseUpdated(i32),
+    Restored(Item, i32),
+    AbsencesError(&'a str, &'a str),
+    AbsenceKind(Kind),
+    AbsenceAdded(u32),
+    AbsenceDeleted,
+    AbsenceNotFound,
+    AbsenceOverlaps(u32),
+    AbsencesTitle(i32),
+    VacationTaken(f64),
+    VacationLeft(f64, f64, u32),
+    LineFailed(usize, &'a str),
+    BatchSummary(usize, usize, usize),
+    FailedLines(&'a str),
+    Skipped(usize),
+}
+
+impl fmt::Display for Msg<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Language::{De, En};
+
+        match (self, language()) {
+            (Msg::LoginSuccess, De) => write!(f, "> Anmeldung erfolgreich"),
+            (Msg::LoginSuccess, En) => write!(f, "> Login successful"),
+            (Msg::LoginFailed, De) => write!(f, "> Anmeldung fehlgeschlagen"),
+            (Msg::LoginFailed, En) => write!(f, "> Login failed"),
+            (Msg::PromptEmail, De) => write!(f, "Gib deine Email-Adresse ein:"),
+            (Msg::PromptEmail, En) => write!(f, "Enter your email address:"),
+            (Msg::PromptPassword, De) => write!(f, "Gib dein Passwort ein:"),
+            (Msg::PromptPassword, En) => write!(f, "Enter your password:"),
+            (Msg::PromptCommand, De) => write!(f, "> Gib ein Kommando ein:"),
+            (Msg::PromptCommand, En) => write!(f, "> Enter a command:"),
+            (Msg::ConfirmDeleteBlock(id), De) => write!(f, "Block {id} löschen? [j/N]"),
+            (Msg::ConfirmDeleteBlock(id), En) => write!(f, "Delete block {id}? [y/N]"),
+            (Msg::ConfirmDeletePause(id), De) => write!(f, "Pause {id} löschen? [j/N]"),
+            (Msg::ConfirmDeletePause(id), En) => write!(f, "Delete pause {id}? [y/N]"),
+            (Msg::DeleteCancelled, De) => write!(f, "> Nicht gelöscht"),
+            (Msg::DeleteCancelled, En) => write!(f, "> Not deleted"),
+            (Msg::ProgramExit, De) => write!(f, "> Programm beendet"),
+            (Msg::ProgramExit, En) => write!(f, "> Program terminated"),
+            (Msg::NetworkError, De) => write!(f, "> Netzwerk Fehler"),
+            (Msg::NetworkError, En) => write!(f, "> Network error"),
+            (Msg::BlockStarted, De) => write!(f, "> Block gestartet"),
+            (Msg::BlockStarted, En) => write!(f, "> Block started"),
+            (Msg::BlockAlreadyActive, De) => write!(f, "> Block bereits aktiv"),
+            (Msg::BlockAlreadyActive, En) => write!(f, "> Block already active"),
+            (Msg::BlockEnded, De) => write!(f, "> Block beendet"),
+            (Msg::BlockEnded, En) => write!(f, "> Block ended"),
+            (Msg::BlockEndFailed, De) => write!(f, "> Kein Block aktiv/Pause noch aktiv"),
+            (Msg::BlockEndFailed, En) => write!(f, "> No block active/pause still active"),
+            (Msg::PauseStarted, De) => write!(f, "> Pause gestartet"),
+            (Msg::PauseStarted, En) => write!(f, "> Pause started"),
+            (Msg::PauseAlreadyActive, De) => write!(f, "> Pause bereits aktiv"),
+            (Msg::PauseAlreadyActive, En) => write!(f, "> Pause already active"),
+            (Msg::PauseEnded, De) => write!(f, "> Pause beendet"),
+            (Msg::PauseEnded, En) => write!(f, "> Pause ended"),
+            (Msg::NoPauseActive, De) => write!(f, "> Keine Pause aktiv"),
+            (Msg::NoPauseActive, En) => write!(f, "> No pause active"),
+            (Msg::CurrentBlock, De) => write!(f, "> Aktueller Block"),
+            (Msg::CurrentBlock, En) => write!(f, "> Current block"),
+            (Msg::NoBlockActive, De) => write!(f, "> Kein Block aktiv"),
+            (Msg::NoBlockActive, En) => write!(f, "> No block active"),
+            (Msg::AllBlocks, De) => write!(f, "> Alle Blöcke"),
+            (Msg::AllBlocks, En) => write!(f, "> All blocks"),
+            (Msg::NoBlocks, De) => write!(f, "> Keine Blöcke"),
+            (Msg::NoBlocks, En) => write!(f, "> No blocks"),
+            (Msg::BlockDeleted, De) => write!(f, "Block gelöscht"),
+            (Msg::BlockDeleted, En) => write!(f, "Block deleted"),
+            (Msg::BlockNotFound, De) => write!(f, "Block nicht gefunden"),
+            (Msg::BlockNotFound, En) => write!(f, "Block not found"),
+            (Msg::BlockUpdated, De) => write!(f, "Block angepasst"),
+            (Msg::BlockUpdated, En) => write!(f, "Block updated"),
+            (Msg::PauseDeleted, De) => write!(f, "Pause gelöscht"),
+            (Msg::PauseDeleted, En) => write!(f, "Pause deleted"),
+            (Msg::PauseNotFound, De) => write!(f, "Pause nicht gefunden"),
+            (Msg::PauseNotFound, En) => write!(f, "Pause not found"),
+            (Msg::PauseUpdated, De) => write!(f, "Pause angepasst"),
+            (Msg::PauseUpdated, En) => write!(f, "Pause updated"),
+            (Msg::UpdateFailed, De) => write!(f, "Fehler beim Anpassen"),
+            (Msg::UpdateFailed, En) => write!(f, "Update failed"),
+            (Msg::BlockActive(id), De) => write!(f, "Block {id} - AKTIV"),
+            (Msg::BlockActive(id), En) => write!(f, "Block {id} - ACTIVE"),
+            (Msg::BlockFinished(id), De) => write!(f, "Block {id} - ABGESCHLOSSEN"),
+            (Msg::BlockFinished(id), En) => write!(f, "Block {id} - FINISHED"),
+            (Msg::PauseActive(id), De) => write!(f, "Pause {id} - AKTIV"),
+            (Msg::PauseActive(id), En) => write!(f, "Pause {id} - ACTIVE"),
+            (Msg::PauseFinished(id), De) => write!(f, "Pause {id} - ABGESCHLOSSEN"),
+            (Msg::PauseFinished(id), En) => write!(f, "Pause {id} - FINISHED"),
+            (Msg::Homeoffice(homeoffice), De) => write!(f, "Homeoffice: {homeoffice}"),
+            (Msg::Homeoffice(homeoffice), En) => write!(f, "Home office: {homeoffice}"),
+            (Msg::ActiveSince(since), De) => write!(f, "Aktiv seit: {since}"),
+            (Msg::ActiveSince(since), En) => write!(f, "Active since: {since}"),
+            (Msg::Start(start), _) => write!(f, "Start: {start}"),
+            (Msg::End(end), _) => write!(f, "End: {end}"),
+            (Msg::Time(time), De) => write!(f, "Zeit: {time}"),
+            (Msg::Time(time), En) => write!(f, "Time: {time}"),
+            (Msg::Idle, De) => write!(f, "Kein Block aktiv"),
+            (Msg::Idle, En) => write!(f, "No block active"),
+            (Msg::NetTime(time), De) => write!(f, "Netto: {time}"),
+            (Msg::NetTime(time), En) => write!(f, "Net: {time}"),
+            (Msg::Today(time), De) => write!(f, "Heute: {time}"),
+            (Msg::Today(time), En) => write!(f, "Today: {time}"),
+            (Msg::ThisWeek(time), De) => write!(f, "Diese Woche: {time}"),
+            (Msg::ThisWeek(time), En) => write!(f, "This week: {time}"),
+            (Msg::TargetReachedIn(time), De) => write!(f, "Soll erreicht in: {time}"),
+            (Msg::TargetReachedIn(time), En) => write!(f, "Target reached in: {time}"),
+            (Msg::TargetExceededBy(time), De) => write!(f, "Soll überschritten um: {time}"),
+            (Msg::TargetExceededBy(time), En) => write!(f, "Target exceeded by: {time}"),
+            (Msg::ProjectedEnd(time), De) => write!(f, "Voraussichtliches Ende: {time}"),
+            (Msg::ProjectedEnd(time), En) => write!(f, "Projected end: {time}"),
+            (Msg::PressAnyKey, De) => write!(f, "Beliebige Taste zum Beenden"),
+            (Msg::PressAnyKey, En) => write!(f, "Press any key to exit"),
+            (Msg::WorkedToday(worked, paused), De) => {
+                write!(f, "Heute gearbeitet: {worked} (Pause {paused})")
+            }
+            (Msg::WorkedToday(worked, paused), En) => {
+                write!(f, "Worked today: {worked} (pause {paused})")
+            }
+            (Msg::TargetAt(target, time), De) => write!(f, "Soll von {target} erreicht: {time}"),
+            (Msg::TargetAt(target, time), En) => write!(f, "Target of {target} reached: {time}"),
+            (Msg::PauseThresholdAt(hours, minutes, time), De) => write!(
+                f,
+                "{hours} Stunden erreicht (danach {minutes} Minuten Pause nötig): {time}"
+            ),
+            (Msg::PauseThresholdAt(hours, minutes, time), En) => write!(
+                f,
+                "{hours} hours reached ({minutes} minutes of pause required after): {time}"
+            ),
+            (Msg::MaximumAt(hours, time), De) => {
+                write!(f, "Höchstarbeitszeit von {hours} Stunden erreicht: {time}")
+            }
+            (Msg::MaximumAt(hours, time), En) => {
+                write!(f, "Maximum working time of {hours} hours reached: {time}")
+            }
+            (Msg::PauseMissing(missing), De) => write!(f, "Noch fehlende Pause: {missing}"),
+            (Msg::PauseMissing(missing), En) => write!(f, "Pause still missing: {missing}"),
+            (Msg::AlreadyReached, De) => write!(f, "bereits erreicht"),
+            (Msg::AlreadyReached, En) => write!(f, "already reached"),
+            (Msg::ReminderWorked(time), De) => write!(f, "Heute {time} Stunden gearbeitet"),
+            (Msg::ReminderWorked(time), En) => write!(f, "Worked {time} hours today"),
+            (Msg::ReminderWithoutPause(time), De) => {
+                write!(f, "Seit {time} Stunden ohne Pause - Zeit für eine Pause")
+            }
+            (Msg::ReminderWithoutPause(time), En) => {
+                write!(f, "{time} hours without a pause - time for a break")
+            }
+            (Msg::ReminderTarget(time), De) => write!(f, "Soll von {time} erreicht"),
+            (Msg::ReminderTarget(time), En) => write!(f, "Target of {time} reached"),
+            (Msg::RemindersActive(count), De) => {
+                write!(f, "> {count} Erinnerungen aktiv, Strg-C zum Beenden")
+            }
+            (Msg::RemindersActive(count), En) => {
+                write!(f, "> {count} reminders active, Ctrl-C to quit")
+            }
+            (Msg::NoReminders, De) => write!(f, "> Keine Erinnerungen konfiguriert"),
+            (Msg::NoReminders, En) => write!(f, "> No reminders configured"),
+            (Msg::HookFailed(hook, err), De) => write!(f, "> Hook {hook} fehlgeschlagen: {err}"),
+            (Msg::HookFailed(hook, err), En) => write!(f, "> Hook {hook} failed: {err}"),
+            (Msg::HookTimeout(seconds), De) => write!(f, "nach {seconds}s abgebrochen"),
+            (Msg::HookTimeout(seconds), En) => write!(f, "killed after {seconds}s"),
+            (Msg::ExitCode(code), De) => write!(f, "Exit-Code {code}"),
+            (Msg::ExitCode(code), En) => write!(f, "exit code {code}"),
+            (Msg::Timeline, De) => write!(f, "Zeitleiste"),
+            (Msg::Timeline, En) => write!(f, "Timeline"),
+            (Msg::DashboardKeys, De) => write!(
+                f,
+                "[b] Block an/aus  [p] Pause an/aus  [h] Homeoffice  [r] Aktualisieren  [q] Beenden"
+            ),
+            (Msg::DashboardKeys, En) => write!(
+                f,
+                "[b] block on/off  [p] pause on/off  [h] home office  [r] refresh  [q] quit"
+            ),
+            (Msg::ReportTitle(year, month), De) => write!(f, "> Bericht {month:02}.{year}"),
+            (Msg::ReportTitle(year, month), En) => write!(f, "> Report {year}-{month:02}"),
+            (Msg::ReportTotal(worked, credited, target, balance), De) => write!(
+                f,
+                "Gesamt: {worked}, Abwesenheiten: {credited}, Soll: {target}, Saldo: {balance}"
+            ),
+            (Msg::ReportTotal(worked, credited, target, balance), En) => write!(
+                f,
+                "Total: {worked}, absences: {credited}, target: {target}, balance: {balance}"
+            ),
+            (Msg::Weekday(weekday), De) => write!(
+                f,
+                "{}",
+                ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"][weekday.num_days_from_monday() as usize]
+            ),
+            (Msg::Weekday(weekday), En) => write!(f, "{weekday}"),
+            (Msg::HomeofficeTitle(year, month), De) => {
+                write!(f, "> Homeoffice {month:02}.{year}")
+            }
+            (Msg::HomeofficeTitle(year, month), En) => {
+                write!(f, "> Home office {year}-{month:02}")
+            }
+            (Msg::TaxTitle(year), De) => write!(f, "> Büro- und Homeoffice-Tage {year}"),
+            (Msg::TaxTitle(year), En) => write!(f, "> Office and home office days {year}"),
+            (Msg::MonthOfYear(year, month), De) => write!(f, "{month:02}.{year}"),
+            (Msg::MonthOfYear(year, month), En) => write!(f, "{year}-{month:02}"),
+            (Msg::TaxDays(office, homeoffice), De) => {
+                write!(f, "Büro: {office}, Homeoffice: {homeoffice}")
+            }
+            (Msg::TaxDays(office, homeoffice), En) => {
+                write!(f, "Office: {office}, home office: {homeoffice}")
+            }
+            (Msg::TaxHomeofficeAllowance(days, cap, amount), De) => {
+                write!(f, "Homeoffice-Pauschale: {days} (höchstens {cap}), {amount}")
+            }
+            (Msg::TaxHomeofficeAllowance(days, cap, amount), En) => {
+                write!(f, "Home office allowance: {days} (at most {cap}), {amount}")
+            }
+            (Msg::Euros(amount), De) => {
+                write!(f, "{} €", format!("{amount:.2}").replace('.', ","))
+            }
+            (Msg::Euros(amount), En) => write!(f, "€{amount:.2}"),
+            (Msg::EarningsTitle(year, month), De) => write!(f, "> Verdienst {month:02}.{year}"),
+            (Msg::EarningsTitle(year, month), En) => write!(f, "> Earnings {year}-{month:02}"),
+            (Msg::EarningsTotal(worked, night, sunday, holiday, amount), De) => write!(
+                f,
+                "Gesamt: {worked} (Nacht {night}, Sonntag {sunday}, Feiertag {holiday}), {amount}"
+            ),
+            (Msg::EarningsTotal(worked, night, sunday, holiday, amount), En) => write!(
+                f,
+                "Total: {worked} (night {night}, Sunday {sunday}, holiday {holiday}), {amount}"
+            ),
+            (Msg::NoRates, De) => write!(f, "> Keine Stundensätze in der Konfiguration"),
+            (Msg::NoRates, En) => write!(f, "> No hourly rates in the config"),
+            (Msg::NoRateOn(date), De) => {
+                write!(f, "> Kein Stundensatz für den {date}, nicht berechnet")
+            }
+            (Msg::NoRateOn(date), En) => write!(f, "> No hourly rate for {date}, not counted"),
+            (Msg::Week(week), De) => write!(f, "KW {week}"),
+            (Msg::Week(week), En) => write!(f, "Week {week}"),
+            (Msg::Month, De) => write!(f, "Monat"),
+            (Msg::Month, En) => write!(f, "Month"),
+            (Msg::DayCount(1), De) => write!(f, "1 Tag"),
+            (Msg::DayCount(days), De) => write!(f, "{days} Tage"),
+            (Msg::DayCount(1), En) => write!(f, "1 day"),
+            (Msg::DayCount(days), En) => write!(f, "{days} days"),
+            (Msg::PlaceShare(office_days, office_time, homeoffice_days, homeoffice_time), De) => {
+                write!(
+                    f,
+                    "Büro: {office_days} ({office_time}), Homeoffice: {homeoffice_days} ({homeoffice_time})"
+                )
+            }
+            (Msg::PlaceShare(office_days, office_time, homeoffice_days, homeoffice_time), En) => {
+                write!(
+                    f,
+                    "Office: {office_days} ({office_time}), home office: {homeoffice_days} ({homeoffice_time})"
+                )
+            }
+            (Msg::HomeofficeQuota(percent, quota), De) => {
+                write!(f, "> Homeoffice-Anteil {percent:.0} %, erlaubt sind {quota} %")
+            }
+            (Msg::HomeofficeQuota(percent, quota), En) => {
+                write!(f, "> Home office share {percent:.0} %, allowed are {quota} %")
+            }
+            (Msg::HomeofficeQuotaWarning(percent, quota), De) => write!(
+                f,
+                "> Achtung: Damit liegt der Homeoffice-Anteil dieses Monats bei {percent:.0} %, erlaubt sind {quota} %"
+            ),
+            (Msg::HomeofficeQuotaWarning(percent, quota), En) => write!(
+                f,
+                "> Warning: This brings this month's home office share to {percent:.0} %, allowed are {quota} %"
+            ),
+            (Msg::Aliases, De) => write!(f, "Aliase:"),
+            (Msg::Aliases, En) => write!(f, "Aliases:"),
+            (Msg::Usage(usage), De) => write!(f, "Verwendung: {usage}"),
+            (Msg::Usage(usage), En) => write!(f, "Usage: {usage}"),
+            (Msg::HelpHint, De) => write!(f, "Gib 'help' ein, um alle Kommandos zu sehen"),
+            (Msg::HelpHint, En) => write!(f, "Enter 'help' to see all commands"),
+            (Msg::ArgDate, De) => write!(f, "{{datum}}"),
+            (Msg::ArgDate, En) => write!(f, "{{date}}"),
+            (Msg::ArgTopic, De) => write!(f, "{{thema}}"),
+            (Msg::ArgTopic, En) => write!(f, "{{topic}}"),
+            (Msg::ArgProject, De) => write!(f, "{{projekt}}"),
+            (Msg::ArgProject, En) => write!(f, "{{project}}"),
+            (Msg::ArgTag, De) => write!(f, "{{schlagwort}}"),
+            (Msg::ArgTag, En) => write!(f, "{{tag}}"),
+            (Msg::ArgText, De) => write!(f, "{{text}}"),
+            (Msg::ArgText, En) => write!(f, "{{text}}"),
+            (Msg::ArgFile, De) => write!(f, "{{datei}}"),
+            (Msg::ArgFile, En) => write!(f, "{{file}}"),
+            (Msg::ArgYear, De) => write!(f, "{{jahr}}"),
+            (Msg::ArgYear, En) => write!(f, "{{year}}"),
+            (Msg::ArgMonth, De) => write!(f, "{{jahr-monat}}"),
+            (Msg::ArgMonth, En) => write!(f, "{{year-month}}"),
+            (Msg::ArgDays, De) => write!(f, "{{datum[..datum]}}"),
+            (Msg::ArgDays, En) => write!(f, "{{date[..date]}}"),
+            (Msg::ArgValue, De) => write!(f, "{{wert}}"),
+            (Msg::ArgValue, En) => write!(f, "{{value}}"),
+            (Msg::HelpBlockStart, De) => write!(f, "Startet einen neuen Block"),
+            (Msg::HelpBlockStart, En) => write!(f, "Starts a new block"),
+            (Msg::HelpBlockStartHomeoffice, De) => {
+                write!(f, "Startet einen neuen Block, wahlweise im Homeoffice")
+            }
+            (Msg::HelpBlockStartHomeoffice, En) => {
+                write!(f, "Starts a new block, optionally in the home office")
+            }
+            (Msg::HelpBlockEnd, De) => write!(f, "Beendet den aktuellen Block"),
+            (Msg::HelpBlockEnd, En) => write!(f, "Ends the current block"),
+            (Msg::HelpBlockDelete, De) => write!(f, "Löscht einen Block"),
+            (Msg::HelpBlockDelete, En) => write!(f, "Deletes a block"),
+            (Msg::HelpBlockCurrent, De) => {
+                write!(f, "Zeigt den aktuellen Block, mit --watch sekündlich aktualisiert")
+            }
+            (Msg::HelpBlockCurrent, En) => {
+                write!(f, "Shows the current block, updated every second with --watch")
+            }
+            (Msg::HelpBlockAll, De) => write!(f, "Zeigt alle Blöcke"),
+            (Msg::HelpBlockAll, En) => write!(f, "Shows all blocks"),
+            (Msg::HelpBlockUpdateStart, De) => write!(f, "Setzt den Start eines Blocks (RFC3339)"),
+            (Msg::HelpBlockUpdateStart, En) => write!(f, "Sets the start of a block (RFC3339)"),
+            (Msg::HelpBlockUpdateEnd, De) => write!(f, "Setzt das Ende eines Blocks (RFC3339)"),
+            (Msg::HelpBlockUpdateEnd, En) => write!(f, "Sets the end of a block (RFC3339)"),
+            (Msg::HelpBlockUpdateHomeoffice, De) => {
+                write!(f, "Legt fest, ob ein Block im Homeoffice war")
+            }
+            (Msg::HelpBlockUpdateHomeoffice, En) => {
+                write!(f, "Sets whether a block was spent in the home office")
+            }
+            (Msg::HelpBlockProject, De) => write!(f, "Ordnet einen Block einem Projekt zu"),
+            (Msg::HelpBlockProject, En) => write!(f, "Assigns a block to a project"),
+            (Msg::HelpBlockTag, De) => write!(f, "Markiert einen Block mit einem Schlagwort"),
+            (Msg::HelpBlockTag, En) => write!(f, "Tags a block"),
+            (Msg::HelpBlockNote, De) => write!(f, "Speichert eine Notiz zu einem Block"),
+            (Msg::HelpBlockNote, En) => write!(f, "Saves a note on a block"),
+            (Msg::HelpBlockSearch, De) => write!(
+                f,
+                "Sucht Blöcke nach Notizen, Projekten und Schlagwörtern"
+            ),
+            (Msg::HelpBlockSearch, En) => {
+                write!(f, "Searches blocks by notes, projects and tags")
+            }
+            (Msg::HelpPauseNote, De) => write!(f, "Speichert eine Notiz zu einer Pause"),
+            (Msg::HelpPauseNote, En) => write!(f, "Saves a note on a pause"),
+            (Msg::HelpPauseStart, De) => write!(f, "Startet eine Pause im aktuellen Block"),
+            (Msg::HelpPauseStart, En) => write!(f, "Starts a pause in the current block"),
+            (Msg::HelpPauseEnd, De) => write!(f, "Beendet die aktuelle Pause"),
+            (Msg::HelpPauseEnd, En) => write!(f, "Ends the current pause"),
+            (Msg::HelpPauseDelete, De) => write!(f, "Löscht eine Pause"),
+            (Msg::HelpPauseDelete, En) => write!(f, "Deletes a pause"),
+            (Msg::HelpPauseUpdateStart, De) => write!(f, "Setzt den Start einer Pause (RFC3339)"),
+            (Msg::HelpPauseUpdateStart, En) => write!(f, "Sets the start of a pause (RFC3339)"),
+            (Msg::HelpPauseUpdateEnd, De) => write!(f, "Setzt das Ende einer Pause (RFC3339)"),
+            (Msg::HelpPauseUpdateEnd, En) => write!(f, "Sets the end of a pause (RFC3339)"),
+            (Msg::HelpStatus, De) => {
+                write!(f, "Zeigt den aktuellen Block in einer Zeile, z.B. für tmux")
+            }
+            (Msg::HelpStatus, En) => {
+                write!(f, "Shows the current block in a single line, e.g. for tmux")
+            }
+            (Msg::HelpDashboard, De) => {
+                write!(f, "Zeigt eine sich aktualisierende Übersicht im Vollbild")
+            }
+            (Msg::HelpDashboard, En) => write!(f, "Shows a live updating full-screen overview"),
+            (Msg::HelpFeierabend, De) => write!(
+                f,
+                "Zeigt, wann Soll, Pausengrenzen und Höchstarbeitszeit erreicht werden"
+            ),
+            (Msg::HelpFeierabend, En) => write!(
+                f,
+                "Shows when the target, the pause thresholds and the maximum working time are reached"
+            ),
+            (Msg::HelpReport, De) => write!(f, "Zeigt Arbeitszeit und Soll des aktuellen Monats"),
+            (Msg::HelpReport, En) => {
+                write!(f, "Shows the working time and target of the current month")
+            }
+            (Msg::HelpReportMonth, De) => write!(f, "Zeigt Arbeitszeit und Soll eines Monats"),
+            (Msg::HelpReportMonth, En) => write!(f, "Shows the working time and target of a month"),
+            (Msg::HelpHomeoffice, De) => {
+                write!(f, "Zeigt Büro- und Homeoffice-Tage des aktuellen Monats")
+            }
+            (Msg::HelpHomeoffice, En) => {
+                write!(f, "Shows the office and home office days of the current month")
+            }
+            (Msg::HelpHomeofficeMonth, De) => {
+                write!(f, "Zeigt Büro- und Homeoffice-Tage eines Monats")
+            }
+            (Msg::HelpHomeofficeMonth, En) => {
+                write!(f, "Shows the office and home office days of a month")
+            }
+            (Msg::HelpExportCsv, De) => write!(f, "Gibt alle Blöcke als CSV aus"),
+            (Msg::HelpExportCsv, En) => write!(f, "Prints all blocks as CSV"),
+            (Msg::HelpExportCsvFile, De) => write!(f, "Schreibt alle Blöcke als CSV in eine Datei"),
+            (Msg::HelpExportCsvFile, En) => write!(f, "Writes all blocks as CSV to a file"),
+            (Msg::HelpExportJson, De) => write!(f, "Gibt alle Blöcke als JSON aus"),
+            (Msg::HelpExportJson, En) => write!(f, "Prints all blocks as JSON"),
+            (Msg::HelpExportJsonFile, De) => {
+                write!(f, "Schreibt alle Blöcke als JSON in eine Datei")
+            }
+            (Msg::HelpExportJsonFile, En) => write!(f, "Writes all blocks as JSON to a file"),
+            (Msg::HelpProjects, De) => {
+                write!(f, "Zeigt die Arbeitszeit je Projekt und Schlagwort im aktuellen Monat")
+            }
+            (Msg::HelpProjects, En) => {
+                write!(f, "Shows the working time per project and tag of the current month")
+            }
+            (Msg::HelpProjectsMonth, De) => {
+                write!(f, "Zeigt die Arbeitszeit je Projekt und Schlagwort in einem Monat")
+            }
+            (Msg::HelpProjectsMonth, En) => {
+                write!(f, "Shows the working time per project and tag of a month")
+            }
+            (Msg::HelpEarnings, De) => write!(f, "Zeigt den Verdienst des aktuellen Monats"),
+            (Msg::HelpEarnings, En) => write!(f, "Shows the earnings of the current month"),
+            (Msg::HelpEarningsMonth, De) => write!(f, "Zeigt den Verdienst eines Monats"),
+            (Msg::HelpEarningsMonth, En) => write!(f, "Shows the earnings of a month"),
+            (Msg::HelpTax, De) => write!(
+                f,
+                "Zeigt Büro- und Homeoffice-Tage dieses Jahres für die Steuererklärung"
+            ),
+            (Msg::HelpTax, En) => write!(
+                f,
+                "Shows this year's office and home office days for the tax return"
+            ),
+            (Msg::HelpTaxYear, De) => write!(
+                f,
+                "Zeigt Büro- und Homeoffice-Tage eines Jahres für die Steuererklärung"
+            ),
+            (Msg::HelpTaxYear, En) => {
+                write!(f, "Shows the office and home office days of a year for the tax return")
+            }
+            (Msg::HelpAbsenceAddVacation, De) => {
+                write!(f, "Trägt Urlaub für einen Tag oder Zeitraum ein")
+            }
+            (Msg::HelpAbsenceAddVacation, En) => write!(f, "Adds vacation for a day or a range"),
+            (Msg::HelpAbsenceAddSick, De) => {
+                write!(f, "Trägt Krankheit für einen Tag oder Zeitraum ein")
+            }
+            (Msg::HelpAbsenceAddSick, En) => write!(f, "Adds sick leave for a day or a range"),
+            (Msg::HelpAbsenceAddComp, De) => write!(
+                f,
+                "Trägt Freizeitausgleich ein, der vom Überstundensaldo abgeht"
+            ),
+            (Msg::HelpAbsenceAddComp, En) => {
+                write!(f, "Adds comp days, which are taken from the overtime")
+            }
+            (Msg::HelpAbsenceList, De) => {
+                write!(f, "Zeigt die Abwesenheiten dieses Jahres und den Resturlaub")
+            }
+            (Msg::HelpAbsenceList, En) => {
+                write!(f, "Shows this year's absences and the remaining vacation")
+            }
+            (Msg::HelpAbsenceDelete, De) => write!(f, "Löscht eine Abwesenheit anhand der ID"),
+            (Msg::HelpAbsenceDelete, En) => write!(f, "Deletes an absence by ID"),
+            (Msg::HelpUndo, De) => {
+                write!(f, "Macht die letzte Änderung oder Löschung rückgängig")
+            }
+            (Msg::HelpUndo, En) => write!(f, "Reverts the last update or deletion"),
+            (Msg::HelpHelp, De) => write!(f, "Zeigt alle Kommandos"),
+            (Msg::HelpHelp, En) => write!(f, "Shows all commands"),
+            (Msg::HelpHelpTopic, De) => write!(f, "Zeigt die Kommandos zu einem Thema"),
+            (Msg::HelpHelpTopic, En) => write!(f, "Shows the commands of a topic"),
+            (Msg::HelpExit, De) => write!(f, "Beendet das Programm"),
+            (Msg::HelpExit, En) => write!(f, "Exits the program"),
+            (Msg::UnknownCommand(command), De) => write!(f, "Unbekanntes Kommando: '{command}'"),
+            (Msg::UnknownCommand(command), En) => write!(f, "Unknown command: '{command}'"),
+            (Msg::UnknownValue(value, allowed), De) => {
+                write!(f, "Unbekannte Angabe '{value}', erlaubt: {allowed}")
+            }
+            (Msg::UnknownValue(value, allowed), En) => {
+                write!(f, "Unknown value '{value}', allowed: {allowed}")
+            }
+            (Msg::MissingArgument(name), De) => write!(f, "Fehlendes Argument: {name}"),
+            (Msg::MissingArgument(name), En) => write!(f, "Missing argument: {name}"),
+            (Msg::InvalidId(value), De) => write!(f, "Ungültige ID: '{value}'"),
+            (Msg::InvalidId(value), En) => write!(f, "Invalid ID: '{value}'"),
+            (Msg::InvalidBool(value), De) => {
+                write!(f, "Ungültiger Wert '{value}', erwartet: true/false")
+            }
+            (Msg::InvalidBool(value), En) => {
+                write!(f, "Invalid value '{value}', expected: true/false")
+            }
+            (Msg::TooManyArguments, De) => write!(f, "Zu viele Argumente"),
+            (Msg::TooManyArguments, En) => write!(f, "Too many arguments"),
+            (Msg::UnknownOption(option), De) => write!(f, "Unbekannte Option: {option}"),
+            (Msg::UnknownOption(option), En) => write!(f, "Unknown option: {option}"),
+            (Msg::MissingOptionValue(option), De) => {
+                write!(f, "Option {option} erwartet einen Wert")
+            }
+            (Msg::MissingOptionValue(option), En) => write!(f, "Option {option} expects a value"),
+            (Msg::UnexpectedOptionValue(option), De) => {
+                write!(f, "Option {option} erwartet keinen Wert")
+            }
+            (Msg::UnexpectedOptionValue(option), En) => {
+                write!(f, "Option {option} does not take a value")
+            }
+            (Msg::UnterminatedQuote, De) => write!(f, "Anführungszeichen nicht geschlossen"),
+            (Msg::UnterminatedQuote, En) => write!(f, "Unterminated quote"),
+            (Msg::AliasLoop(alias), De) => {
+                write!(f, "Alias '{alias}' verweist zu oft auf sich selbst")
+            }
+            (Msg::AliasLoop(alias), En) => write!(f, "Alias '{alias}' refers to itself too often"),
+            (Msg::DidYouMean(suggestion), De) => write!(f, " - meintest du '{suggestion}'?"),
+            (Msg::DidYouMean(suggestion), En) => write!(f, " - did you mean '{suggestion}'?"),
+            (Msg::UnknownArgument(arg), De) => write!(f, "Unbekanntes Argument: {arg}"),
+            (Msg::UnknownArgument(arg), En) => write!(f, "Unknown argument: {arg}"),
+            (Msg::UnexpectedArgument(arg), De) => write!(f, "Unerwartetes Argument: {arg}"),
+            (Msg::UnexpectedArgument(arg), En) => write!(f, "Unexpected argument: {arg}"),
+            (Msg::MissingPasswordCommand, De) => {
+                write!(f, "--password-command erwartet ein Kommando")
+            }
+            (Msg::MissingPasswordCommand, En) => write!(f, "--password-command expects a command"),
+            (Msg::MissingCredentials, De) => write!(
+                f,
+                "Fehlende Zugangsdaten: kein Terminal verbunden und WORK_HOURS_EMAIL/WORK_HOURS_PASSWORD nicht gesetzt"
+            ),
+            (Msg::MissingCredentials, En) => write!(
+                f,
+                "Missing credentials: no terminal attached and WORK_HOURS_EMAIL/WORK_HOURS_PASSWORD not set"
+            ),
+            (Msg::PasswordCommandFailed(code), De) => {
+                write!(f, "Passwort-Kommando mit Exit-Code {code} fehlgeschlagen")
+            }
+            (Msg::PasswordCommandFailed(code), En) => {
+                write!(f, "Password command failed with exit code {code}")
+            }
+            (Msg::PasswordCommandKilled, De) => {
+                write!(f, "Passwort-Kommando wurde durch ein Signal beendet")
+            }
+            (Msg::PasswordCommandKilled, En) => {
+                write!(f, "Password command was terminated by a signal")
+            }
+            (Msg::UnknownShell(shell, shells), De) => {
+                write!(f, "Unbekannte Shell: {shell}, erlaubt sind {shells}")
+            }
+            (Msg::UnknownShell(shell, shells), En) => {
+                write!(f, "Unknown shell: {shell}, allowed are {shells}")
+            }
+            (Msg::ConfigError(path, err), De) => {
+                write!(f, "> Fehler in der Konfiguration {path}: {err}")
+            }
+            (Msg::ConfigError(path, err), En) => write!(f, "> Error in the config {path}: {err}"),
+            (Msg::ReadError(err), De) => write!(f, "> Fehler beim Lesen: {err}"),
+            (Msg::ReadError(err), En) => write!(f, "> Error while reading: {err}"),
+            (Msg::WriteError(err), De) => write!(f, "> Fehler beim Schreiben: {err}"),
+            (Msg::WriteError(err), En) => write!(f, "> Error while writing: {err}"),
+            (Msg::AnnotationsError(path, err), De) => {
+                write!(f, "> Fehler in den Projekten und Schlagwörtern {path}: {err}")
+            }
+            (Msg::AnnotationsError(path, err), En) => {
+                write!(f, "> Error in the projects and tags {path}: {err}")
+            }
+            (Msg::ProjectSet(id, project), De) => {
+                write!(f, "> Projekt von Block {id} ist jetzt '{project}'")
+            }
+            (Msg::ProjectSet(id, project), En) => {
+                write!(f, "> Project of block {id} is now '{project}'")
+            }
+            (Msg::TagAdded(id, tag), De) => write!(f, "> Block {id} mit Schlagwort '{tag}' markiert"),
+            (Msg::TagAdded(id, tag), En) => write!(f, "> Block {id} tagged with '{tag}'"),
+            (Msg::Project(project), De) => write!(f, "Projekt: {project}"),
+            (Msg::Project(project), En) => write!(f, "Project: {project}"),
+            (Msg::Tags(tags), De) => write!(f, "Schlagwörter: {tags}"),
+            (Msg::Tags(tags), En) => write!(f, "Tags: {tags}"),
+            (Msg::ProjectsTitle(year, month), De) => write!(f, "> Projekte {month:02}.{year}"),
+            (Msg::ProjectsTitle(year, month), En) => write!(f, "> Projects {year}-{month:02}"),
+            (Msg::NoProject, De) => write!(f, "(ohne Projekt)"),
+            (Msg::NoProject, En) => write!(f, "(no project)"),
+            (Msg::TagsTitle, De) => write!(f, "Schlagwörter:"),
+            (Msg::TagsTitle, En) => write!(f, "Tags:"),
+            (Msg::Note(note), De) => write!(f, "Notiz: {note}"),
+            (Msg::Note(note), En) => write!(f, "Note: {note}"),
+            (Msg::NoteSaved, De) => write!(f, "> Notiz gespeichert"),
+            (Msg::NoteSaved, En) => write!(f, "> Note saved"),
+            (Msg::SearchResults(text), De) => write!(f, "> Blöcke mit '{text}'"),
+            (Msg::SearchResults(text), En) => write!(f, "> Blocks with '{text}'"),
+            (Msg::NothingFound, De) => write!(f, "Nichts gefunden"),
+            (Msg::NothingFound, En) => write!(f, "Nothing found"),
+            (Msg::Exported(count, path), De) => write!(f, "> {count} Blöcke nach {path} exportiert"),
+            (Msg::Exported(count, path), En) => write!(f, "> {count} blocks exported to {path}"),
+            (Msg::JournalError(path, err), De) => {
+                write!(f, "> Fehler im Änderungsprotokoll {path}: {err}")
+            }
+            (Msg::JournalError(path, err), En) => write!(f, "> Error in the journal {path}: {err}"),
+            (Msg::NothingToUndo, De) => write!(f, "> Nichts rückgängig zu machen"),
+            (Msg::NothingToUndo, En) => write!(f, "> Nothing to undo"),
+            (Msg::NotJournaled, De) => write!(
+                f,
+                "> Vorheriger Stand unbekannt, die Änderung kann nicht rückgängig gemacht werden"
+            ),
+            (Msg::NotJournaled, En) => write!(
+                f,
+                "> Previous state unknown, the change cannot be undone"
+            ),
+            (Msg::UndoFailed, De) => write!(f, "> Rückgängig machen fehlgeschlagen"),
+            (Msg::UndoFailed, En) => write!(f, "> Undo failed"),
+            (Msg::UndoRolledBack, De) => {
+                write!(f, "> Teilweise wiederhergestellte Einträge wurden wieder gelöscht")
+            }
+            (Msg::UndoRolledBack, En) => {
+                write!(f, "> Partly restored entries were deleted again")
+            }
+            (Msg::UndoRollbackFailed, De) => write!(
+                f,
+                "> Teilweise wiederhergestellte Einträge konnten nicht gelöscht werden"
+            ),
+            (Msg::UndoRollbackFailed, En) => {
+                write!(f, "> Partly restored entries could not be deleted")
+            }
+            (Msg::UndoBlockActive, De) => {
+                write!(f, "> Ein gelöschter Block kann nur ohne laufenden Block wiederhergestellt werden")
+            }
+            (Msg::UndoBlockActive, En) => {
+                write!(f, "> A deleted block can only be restored while no block is active")
+            }
+            (Msg::UndoPauseOutsideBlock(id), De) => write!(
+                f,
+                "> Die Pause kann nur wiederhergestellt werden, während Block {id} ohne Pause läuft"
+            ),
+            (Msg::UndoPauseOutsideBlock(id), En) => write!(
+                f,
+                "> The pause can only be restored while block {id} is active without a pause"
+            ),
+            (Msg::UndoneBlockDeleted(id), De) => write!(f, "> Löschen von Block {id} rückgängig gemacht"),
+            (Msg::UndoneBlockDeleted(id), En) => write!(f, "> Deletion of block {id} undone"),
+            (Msg::UndoneBlockUpdated(id), De) => write!(f, "> Änderung von Block {id} rückgängig gemacht"),
+            (Msg::UndoneBlockUpdated(id), En) => write!(f, "> Update of block {id} undone"),
+            (Msg::UndonePauseDeleted(id), De) => write!(f, "> Löschen von Pause {id} rückgängig gemacht"),
+            (Msg::UndonePauseDeleted(id), En) => write!(f, "> Deletion of pause {id} undone"),
+            (Msg::UndonePauseUpdated(id), De) => write!(f, "> Änderung von Pause {id} rückgängig gemacht"),
+            (Msg::UndonePauseUpdated(id), En) => write!(f, "> Update of pause {id} undone"),
+            (Msg::Restored(Item::Block, id), De) => write!(f, "> Wiederhergestellt als Block {id}"),
+            (Msg::Restored(Item::Block, id), En) => write!(f, "> Restored as block {id}"),
+            (Msg::Restored(Item::Pause, id), De) => write!(f, "> Wiederhergestellt als Pause {id}"),
+            (Msg::Restored(Item::Pause, id), En) => write!(f, "> Restored as pause {id}"),
+            (Msg::AbsencesError(path, err), De) => {
+                write!(f, "> Fehler in den Abwesenheiten {path}: {err}")
+            }
+            (Msg::AbsencesError(path, err), En) => {
+                write!(f, "> Error in the absences {path}: {err}")
+            }
+            (Msg::AbsenceKind(Kind::Vacation), De) => write!(f, "Urlaub"),
+            (Msg::AbsenceKind(Kind::Vacation), En) => write!(f, "Vacation"),
+            (Msg::AbsenceKind(Kind::Sick), De) => write!(f, "Krank"),
+            (Msg::AbsenceKind(Kind::Sick), En) => write!(f, "Sick"),
+            (Msg::AbsenceKind(Kind::Comp), De) => write!(f, "Freizeitausgleich"),
+            (Msg::AbsenceKind(Kind::Comp), En) => write!(f, "Comp day"),
+            (Msg::AbsenceAdded(id), De) => write!(f, "> Abwesenheit {id} eingetragen"),
+            (Msg::AbsenceAdded(id), En) => write!(f, "> Absence {id} added"),
+            (Msg::AbsenceDeleted, De) => write!(f, "> Abwesenheit gelöscht"),
+            (Msg::AbsenceDeleted, En) => write!(f, "> Absence deleted"),
+            (Msg::AbsenceNotFound, De) => write!(f, "> Abwesenheit nicht gefunden"),
+            (Msg::AbsenceNotFound, En) => write!(f, "> Absence not found"),
+            (Msg::AbsenceOverlaps(id), De) => {
+                write!(f, "> Überschneidet sich mit Abwesenheit {id}")
+            }
+            (Msg::AbsenceOverlaps(id), En) => write!(f, "> Overlaps with absence {id}"),
+            (Msg::AbsencesTitle(year), De) => write!(f, "> Abwesenheiten {year}"),
+            (Msg::AbsencesTitle(year), En) => write!(f, "> Absences {year}"),
+            (Msg::VacationTaken(taken), De) => {
+                write!(f, "Urlaub genommen: {} Tage", decimal_comma(*taken))
+            }
+            (Msg::VacationTaken(taken), En) => write!(f, "Vacation taken: {taken} days"),
+            (Msg::VacationLeft(taken, left, total), De) => write!(
+                f,
+                "Urlaub genommen: {} von {total} Tagen, verbleibend: {}",
+                decimal_comma(*taken),
+                decimal_comma(*left)
+            ),
+            (Msg::VacationLeft(taken, left, total), En) => write!(
+                f,
+                "Vacation taken: {taken} of {total} days, remaining: {left}"
+            ),
+            (Msg::LineFailed(number, line), De) => {
+                write!(f, "> Zeile {number} fehlgeschlagen: {line}")
+            }
+            (Msg::LineFailed(number, line), En) => write!(f, "> Line {number} failed: {line}"),
+            (Msg::BatchSummary(total, succeeded, failed), De) => write!(
+                f,
+                "> {total} Kommandos ausgeführt, {succeeded} erfolgreich, {failed} fehlgeschlagen"
+            ),
+            (Msg::BatchSummary(total, succeeded, failed), En) => write!(
+                f,
+                "> {total} commands executed, {succeeded} successful, {failed} failed"
+            ),
+            (Msg::FailedLines(lines), De) => write!(f, " (Zeilen {lines})"),
+            (Msg::FailedLines(lines), En) => write!(f, " (lines {lines})"),
+            (Msg::Skipped(skipped), De) => write!(f, "> {skipped} Kommandos übersprungen"),
+            (Msg::Skipped(skipped), En) => write!(f, "> {skipped} commands skipped"),
+        }
+    }
+}
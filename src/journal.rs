@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    annotations,
+    auth::Token,
+    block::{Block, Pause},
+    data::{load_json, save_json},
+    fetch::{ActionHandler, ResponseHandler},
+    i18n::Msg,
+    prompt::{error_text, success_text},
+};
+
+const JOURNAL_FILE: &str = "journal.json";
+const JOURNAL_SIZE: usize = 50;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Item {
+    Block,
+    Pause,
+}
+
+/// A change of a block or pause, with its state before the change.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum Change {
+    BlockDeleted { block: Block },
+    BlockUpdated { block: Block },
+    PauseDeleted { block_id: i32, pause: Pause },
+    PauseUpdated { pause: Pause },
+}
+
+impl Change {
+    /// The state of the block or pause with the ID in the blocks fetched
+    /// before the change.
+    pub fn before(item: Item, id: i32, deleted: bool, blocks: &[Block]) -> Option<Change> {
+        match item {
+            Item::Block => {
+                let block = blocks.iter().find(|block| block.id == id)?.clone();
+                Some(match deleted {
+                    true => Change::BlockDeleted { block },
+                    false => Change::BlockUpdated { block },
+                })
+            }
+            Item::Pause => blocks.iter().find_map(|block| {
+                let pause = block.pauses().iter().find(|pause| pause.id == id)?.clone();
+                Some(match deleted {
+                    true => Change::PauseDeleted {
+                        block_id: block.id,
+                        pause,
+                    },
+                    false => Change::PauseUpdated { pause },
+                })
+            }),
+        }
+    }
+
+    /// Points the change to the new ID of a restored block or pause.
+    fn rename(&mut self, item: Item, old: i32, new: i32) {
+        match (self, item) {
+            (Change::BlockDeleted { block } | Change::BlockUpdated { block }, Item::Block)
+                if block.id == old =>
+            {
+                block.id = new
+            }
+            (Change::PauseDeleted { block_id, .. }, Item::Block) if *block_id == old => {
+                *block_id = new
+            }
+            (Change::PauseDeleted { pause, .. } | Change::PauseUpdated { pause }, Item::Pause)
+                if pause.id == old =>
+            {
+                pause.id = new
+            }
+            _ => {}
+        }
+    }
+
+    fn undone(&self) -> Msg<'static> {
+        match self {
+            Change::BlockDeleted { block } => Msg::UndoneBlockDeleted(block.id),
+            Change::BlockUpdated { block } => Msg::UndoneBlockUpdated(block.id),
+            Change::PauseDeleted { pause, .. } => Msg::UndonePauseDeleted(pause.id),
+            Change::PauseUpdated { pause } => Msg::UndonePauseUpdated(pause.id),
+        }
+    }
+}
+
+/// The latest changes of blocks and pauses, kept in the data directory so
+/// that `undo` can restore them.
+#[derive(Serialize, Deserialize, Default)]
+struct Journal {
+    changes: Vec<Change>,
+}
+
+impl Journal {
+    fn load() -> Journal {
+        load_json(JOURNAL_FILE, |path, err| Msg::JournalError(path, err))
+    }
+
+    fn save(&self) -> bool {
+        save_json(JOURNAL_FILE, self, None)
+    }
+}
+
+/// Adds a change to the journal, dropping the oldest ones beyond
+/// `JOURNAL_SIZE`.
+pub fn record(change: Change) {
+    let mut journal = Journal::load();
+    journal.changes.push(change);
+    let excess = journal.changes.len().saturating_sub(JOURNAL_SIZE);
+    journal.changes.drain(..excess);
+
+    journal.save();
+}
+
+/// Restores the state before the last change and removes it from the
+/// journal. Restored blocks and pauses get new IDs, their annotations and
+/// older changes of them are moved to these. If restoring fails, the
+/// recreated blocks and pauses are deleted again and the change is kept;
+/// if that fails as well, they are kept and the change is removed so that
+/// it is not restored twice.
+pub fn undo(token: &Token, action_handler: &mut ActionHandler) -> bool {
+    let mut journal = Journal::load();
+    let Some(change) = journal.changes.pop() else {
+        println!("{}", error_text(&Msg::NothingToUndo.to_string()));
+        return false;
+    };
+
+    let current = match action_handler.get_current_block(token) {
+        Ok((block, status)) if status.is_success() => Some(block),
+        _ => None,
+    };
+    match (&change, &current) {
+        (Change::BlockDeleted { .. }, Some(_)) => {
+            println!("{}", error_text(&Msg::UndoBlockActive.to_string()));
+            return false;
+        }
+        (Change::PauseDeleted { block_id, .. }, current)
+            if current
+                .as_ref()
+                .is_none_or(|block| block.id != *block_id || block.active_pause().is_some()) =>
+        {
+            println!(
+                "{}",
+                error_text(&Msg::UndoPauseOutsideBlock(*block_id).to_string())
+            );
+            return false;
+        }
+        _ => {}
+    }
+
+    let mut created = Vec::new();
+    let restored = action_handler
+        .restore(&change, &mut created, token)
+        .handle_response(change.undone(), Msg::UndoFailed)
+        .is_some();
+    let rolled_back = !restored
+        && (created.is_empty()
+            || action_handler
+                .discard(&created, token)
+                .handle_response(Msg::UndoRolledBack, Msg::UndoRollbackFailed)
+                .is_some());
+
+    if rolled_back {
+        // Nothing changed on the server, the change can be undone again.
+        journal.changes.push(change);
+    } else {
+        for &(item, old, new) in &created {
+            match item {
+                Item::Block => annotations::move_block(old, new),
+                Item::Pause => annotations::move_pause(old, new),
+            }
+            journal
+                .changes
+                .iter_mut()
+                .for_each(|change| change.rename(item, old, new));
+            println!("{}", success_text(&Msg::Restored(item, new).to_string()));
+        }
+    }
+
+    journal.save() && restored
+}
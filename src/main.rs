@@ -1,113 +1,642 @@
-use block::visualize_blocks;
-use fetch::ResponseHandler;
-use parse::{parse_command, Command};
-use prompt::{error_text, prompt_command, prompt_login, success_text};
+use args::Args;
+use auth::{Authorizer, Token};
+use batch::{BatchOptions, BatchSummary};
+use block::{visualize_blocks, Block};
+use colored::ColoredString;
+use config::Config;
+use error::ParseError;
+use fetch::{ActionHandler, ResponseHandler};
+use i18n::Msg;
+use parse::{parse_line, parse_words, Command, Word};
+use prompt::{confirm, error_text, is_interactive, success_text, CommandPrompt};
+use reminder::Reminders;
 
+mod absence;
+mod annotations;
+mod args;
 mod auth;
+mod batch;
 mod block;
+mod complete;
+mod config;
+mod dashboard;
+mod data;
+mod display;
+mod earnings;
 mod error;
+mod export;
 mod fetch;
+mod holidays;
+mod homeoffice;
+mod hooks;
+mod i18n;
+mod journal;
 mod parse;
+mod projection;
+mod projects;
 mod prompt;
+mod reminder;
+mod report;
+mod rounding;
+mod shell_completions;
+mod status;
+mod tax;
 mod time;
+mod watch;
+mod work;
 
 fn main() {
-    let mut authorizer = auth::Authorizer::new();
-    let mut action_handler = fetch::ActionHandler::new();
+    let mut args = match args::parse_args() {
+        Ok(args) => args,
+        Err(err) => {
+            println!("{}", error_text(&format!("> {err}")));
+            std::process::exit(2);
+        }
+    };
 
-    loop {
-        if authorizer.login_necessary() {
-            let (email, password) = prompt_login();
-            match authorizer.login(email, password) {
-                Ok(_) => println!("{}", success_text("> Anmeldung erfolgreich")),
-                Err(_) => println!("{}", error_text("> Anmeldung fehlgeschlagen")),
-            }
-        } else {
-            let token = authorizer.token().expect("> Kein Token gefunden");
-            let command = prompt_command();
-
-            match parse_command(command.trim()) {
-                Command::BlockStart(homeoffice) => {
-                    action_handler
-                        .start_block(token, homeoffice)
-                        .handle_response("> Block gestartet", "> Block bereits aktiv");
-                }
-                Command::BlockEnd => {
-                    action_handler
-                        .end_block(token)
-                        .handle_response("> Block beendet", "> Kein Block aktiv/Pause noch aktiv");
-                }
-                Command::PauseStart => {
-                    action_handler
-                        .start_pause(token)
-                        .handle_response("> Pause gestartet", "> Pause bereits aktiv");
-                }
-                Command::PauseEnd => {
-                    action_handler
-                        .end_pause(token)
-                        .handle_response("> Pause beendet", "> Keine Pause aktiv");
+    let config = Config::load();
+    i18n::set_language(config.language.unwrap_or_else(i18n::Language::from_env));
+
+    if let [name, shell] = args.command.as_slice() {
+        if name == "completions" {
+            match shell_completions::generate_completions(shell) {
+                Ok(script) => print!("{script}"),
+                Err(err) => {
+                    println!("{}", error_text(&format!("> {err}")));
+                    std::process::exit(2);
                 }
-                Command::BlockCurrent => {
-                    let block = action_handler
-                        .get_current_block(token)
-                        .handle_response("> Aktueller Block", "> Kein Block aktiv");
+            }
+            return;
+        }
+    }
 
-                    if let Some(block) = block {
-                        block.display();
-                    }
+    let mut authorizer = Authorizer::new();
+    let mut action_handler = ActionHandler::new();
+    action_handler.set_hooks(config.hooks.clone());
+
+    if args.command.first().is_some_and(|name| name == "remind") {
+        run_reminders(&args, &config, &mut authorizer, &mut action_handler);
+    }
+
+    let batch_options = match args.command.split_first() {
+        Some((name, run_args)) if name == "run" => Some(batch::parse_batch_args(run_args)),
+        None if !is_interactive() => Some(Ok(BatchOptions {
+            path: None,
+            stop_on_error: false,
+        })),
+        _ => None,
+    };
+
+    if let Some(options) = batch_options {
+        let options = options.unwrap_or_else(|err| {
+            println!("{}", error_text(&format!("> {err}")));
+            std::process::exit(2);
+        });
+        let summary = run_batch(
+            &options,
+            &mut args,
+            &config,
+            &mut authorizer,
+            &mut action_handler,
+        );
+        hooks::wait_for_hooks();
+        std::process::exit(if summary.success() { 0 } else { 1 });
+    }
+
+    if !args.command.is_empty() {
+        let words = args.command.iter().map(|arg| Word::unquoted(arg)).collect();
+        let commands = match parse_words(words, &config.aliases) {
+            Ok(commands) => commands,
+            Err(err) => {
+                print_parse_error(&err);
+                std::process::exit(2);
+            }
+        };
+
+        if !commands.iter().any(Command::needs_login) {
+            let success = execute_all_offline(commands, &config);
+            std::process::exit(if success == Some(false) { 1 } else { 0 });
+        }
+
+        // The status line is read by status bars, which must neither wait for
+        // a login nor show its messages.
+        let status_only = commands
+            .iter()
+            .all(|command| matches!(command, Command::Status(_)));
+        if let [Command::Status(format)] = commands.as_slice() {
+            if status::print_cached_status(format.as_deref(), &config) {
+                return;
+            }
+        }
+
+        login(&mut authorizer, &args, &config, status_only);
+        let token = authorizer.token().expect("> Kein Token gefunden");
+        let success = execute_all(commands, token, &config, &mut action_handler);
+        hooks::wait_for_hooks();
+        std::process::exit(if success == Some(false) { 1 } else { 0 });
+    }
+
+    let mut command_prompt = CommandPrompt::new();
+    command_prompt.set_aliases(config.aliases.keys().cloned().collect());
+
+    loop {
+        login(&mut authorizer, &args, &config, false);
+
+        let token = authorizer.token().expect("> Kein Token gefunden");
+        let (block_ids, pause_ids) = action_handler.cached_ids();
+        command_prompt.set_ids(block_ids, pause_ids);
+
+        let Some(command) = command_prompt.prompt_command() else {
+            break;
+        };
+
+        let command = command.trim();
+        if command.is_empty() {
+            continue;
+        }
+
+        match parse_line(command, &config.aliases) {
+            Ok(commands) => {
+                if execute_all(commands, token, &config, &mut action_handler).is_none() {
+                    println!("{}", success_text(&Msg::ProgramExit.to_string()));
+                    break;
                 }
-                Command::BlockAll => {
-                    let blocks = action_handler
-                        .get_all_blocks(token)
-                        .handle_response("> Alle Blöcke", "> Keine Blöcke");
+            }
+            Err(err) => print_parse_error(&err),
+        }
+    }
+
+    hooks::wait_for_hooks();
+}
+
+/// Runs the commands of a file or stdin line by line and prints a summary.
+fn run_batch(
+    options: &BatchOptions,
+    args: &mut Args,
+    config: &Config,
+    authorizer: &mut Authorizer,
+    action_handler: &mut ActionHandler,
+) -> BatchSummary {
+    // The password comes first when the commands are read from stdin too.
+    if options.path.is_none() {
+        if let Err(err) = args.login.read_password_ahead() {
+            println!(
+                "{}",
+                error_text(&Msg::ReadError(&err.to_string()).to_string())
+            );
+            std::process::exit(2);
+        }
+    }
+
+    let lines = batch::read_commands(options.path.as_deref()).unwrap_or_else(|err| {
+        println!(
+            "{}",
+            error_text(&Msg::ReadError(&err.to_string()).to_string())
+        );
+        std::process::exit(2);
+    });
+
+    let mut summary = BatchSummary::default();
 
-                    if let Some(blocks) = blocks {
-                        visualize_blocks(blocks);
+    for (i, (number, line)) in lines.iter().enumerate() {
+        let success = match parse_line(line, &config.aliases) {
+            Ok(commands) => {
+                let result = match commands.iter().any(Command::needs_login) {
+                    true => {
+                        login(authorizer, args, config, false);
+                        let token = authorizer.token().expect("> Kein Token gefunden");
+                        execute_all(commands, token, config, action_handler)
+                    }
+                    false => execute_all_offline(commands, config),
+                };
+                match result {
+                    Some(success) => success,
+                    None => {
+                        summary.succeeded += 1;
+                        summary.skipped = lines.len() - i - 1;
+                        break;
                     }
                 }
-                Command::BlockDelete(id) => {
-                    action_handler
-                        .delete_block(id, token)
-                        .handle_response("Block gelöscht", "Block nicht gefunden");
-                }
-                Command::BlockUpdateStart((id, start)) => {
-                    action_handler
-                        .update_block_start(id, &start, token)
-                        .handle_response("Block angepasst", "Fehler beim Anpassen");
-                }
-                Command::BlockUpdateEnd((id, end)) => {
-                    action_handler
-                        .update_block_end(id, &end, token)
-                        .handle_response("Block angepasst", "Fehler beim Anpassen");
-                }
-                Command::BlockUpdateHomeoffice((id, homeoffice)) => {
-                    action_handler
-                        .update_block_homeoffice(id, homeoffice, token)
-                        .handle_response("Block angepasst", "Fehler beim Anpassen");
-                }
-                Command::PauseDelete(id) => {
-                    action_handler
-                        .delete_pause(id, token)
-                        .handle_response("Pause gelöscht", "Pause nicht gefunden");
-                }
-                Command::PauseUpdateStart((id, start)) => {
-                    action_handler
-                        .update_pause_start(id, &start, token)
-                        .handle_response("Pause angepasst", "Fehler beim Anpassen");
-                }
-                Command::PauseUpdateEnd((id, end)) => {
-                    action_handler
-                        .update_pause_end(id, &end, token)
-                        .handle_response("Pause angepasst", "Fehler beim Anpassen");
-                }
-                Command::Exit => {
-                    println!("{}", success_text("> Programm beendet"));
-                    break;
-                }
-                Command::Unknown => {
-                    println!("{}", error_text("Unbekanntes Kommando"))
+            }
+            Err(err) => {
+                print_parse_error(&err);
+                false
+            }
+        };
+
+        if success {
+            summary.succeeded += 1;
+            continue;
+        }
+
+        println!(
+            "{}",
+            error_text(&Msg::LineFailed(*number, line).to_string())
+        );
+        summary.failed_lines.push(*number);
+
+        if options.stop_on_error {
+            summary.skipped = lines.len() - i - 1;
+            break;
+        }
+    }
+
+    summary.print();
+    summary
+}
+
+/// Checks the current block periodically and fires the configured reminders
+/// until the process is terminated.
+fn run_reminders(
+    args: &Args,
+    config: &Config,
+    authorizer: &mut Authorizer,
+    action_handler: &mut ActionHandler,
+) -> ! {
+    if config.reminders.is_empty() {
+        println!("{}", error_text(&Msg::NoReminders.to_string()));
+        std::process::exit(2);
+    }
+    println!(
+        "{}",
+        success_text(&Msg::RemindersActive(config.reminders.len()).to_string())
+    );
+
+    let mut reminders = Reminders::default();
+
+    loop {
+        login(authorizer, args, config, false);
+        let token = authorizer.token().expect("> Kein Token gefunden");
+
+        action_handler.clear_cache();
+        if let Ok((current, status)) = action_handler.get_current_block(token) {
+            if status.is_success() {
+                let blocks = work::with_current_block(current.clone(), token, action_handler);
+                reminders.check(&current, &blocks, config);
+            }
+        }
+
+        std::thread::sleep(reminder::CHECK_INTERVAL);
+    }
+}
+
+/// Logs in until a valid token exists, exits if no credentials can be obtained
+/// without a terminal. With `to_stderr` the messages stay out of the output.
+fn login(authorizer: &mut Authorizer, args: &Args, config: &Config, to_stderr: bool) {
+    let report = |message: ColoredString| match to_stderr {
+        true => eprintln!("{message}"),
+        false => println!("{message}"),
+    };
+
+    while authorizer.login_necessary() {
+        match authorizer.login(&args.login) {
+            Ok(_) => {
+                report(success_text(&Msg::LoginSuccess.to_string()));
+                hooks::fire(&config.hooks, hooks::Event::Login, None);
+            }
+            Err(err) => {
+                report(error_text(&Msg::LoginFailed.to_string()));
+                if !is_interactive() {
+                    eprintln!("{err}");
+                    std::process::exit(1);
                 }
             }
         }
     }
 }
+
+/// Runs the commands in order until one fails and returns whether all of them
+/// were successful, or `None` if one of them was `exit`.
+fn execute_all(
+    commands: Vec<Command>,
+    token: &Token,
+    config: &Config,
+    action_handler: &mut ActionHandler,
+) -> Option<bool> {
+    for command in commands {
+        if let Command::Exit = command {
+            return None;
+        }
+        if !execute(command, token, config, action_handler) {
+            return Some(false);
+        }
+    }
+
+    Some(true)
+}
+
+/// Like `execute_all` for commands that do not talk to the server, so no
+/// token is needed.
+fn execute_all_offline(commands: Vec<Command>, config: &Config) -> Option<bool> {
+    for command in commands {
+        if let Command::Exit = command {
+            return None;
+        }
+        if !execute_offline(command, config) {
+            return Some(false);
+        }
+    }
+
+    Some(true)
+}
+
+/// Runs a single command and returns whether it was successful.
+fn execute(
+    command: Command,
+    token: &Token,
+    config: &Config,
+    action_handler: &mut ActionHandler,
+) -> bool {
+    match command {
+        Command::BlockStart((homeoffice, project)) => {
+            if homeoffice {
+                homeoffice::warn_about_quota(token, config, action_handler);
+            }
+            let started = action_handler
+                .start_block(token, homeoffice)
+                .handle_response(Msg::BlockStarted, Msg::BlockAlreadyActive)
+                .is_some();
+
+            match (started, project) {
+                (true, Some(project)) => match action_handler.get_current_block(token) {
+                    Ok((block, status)) if status.is_success() => {
+                        annotations::set_project(block.id, &project)
+                    }
+                    _ => false,
+                },
+                _ => started,
+            }
+        }
+        Command::BlockEnd => action_handler
+            .end_block(token)
+            .handle_response(Msg::BlockEnded, Msg::BlockEndFailed)
+            .is_some(),
+        Command::PauseStart => action_handler
+            .start_pause(token)
+            .handle_response(Msg::PauseStarted, Msg::PauseAlreadyActive)
+            .is_some(),
+        Command::PauseEnd => action_handler
+            .end_pause(token)
+            .handle_response(Msg::PauseEnded, Msg::NoPauseActive)
+            .is_some(),
+        Command::BlockCurrent(true) => watch::watch_current_block(token, config, action_handler),
+        Command::BlockCurrent(false) => {
+            let block = action_handler
+                .get_current_block(token)
+                .handle_response(Msg::CurrentBlock, Msg::NoBlockActive);
+
+            if let Some(ref block) = block {
+                block.display(&config.display);
+            }
+            block.is_some()
+        }
+        Command::BlockAll => {
+            let blocks = action_handler
+                .get_all_blocks(token)
+                .handle_response(Msg::AllBlocks, Msg::NoBlocks);
+
+            let success = blocks.is_some();
+            if let Some(blocks) = blocks {
+                visualize_blocks(blocks, &config.display);
+            }
+            success
+        }
+        Command::BlockDelete((id, yes)) => {
+            confirm_block_delete(id, yes, token, config, action_handler)
+                && action_handler
+                    .delete_block(id, token)
+                    .handle_response(Msg::BlockDeleted, Msg::BlockNotFound)
+                    .is_some()
+        }
+        Command::BlockUpdateStart((id, start)) => action_handler
+            .update_block_start(id, &start, token)
+            .handle_response(Msg::BlockUpdated, Msg::UpdateFailed)
+            .is_some(),
+        Command::BlockUpdateEnd((id, end)) => action_handler
+            .update_block_end(id, &end, token)
+            .handle_response(Msg::BlockUpdated, Msg::UpdateFailed)
+            .is_some(),
+        Command::BlockUpdateHomeoffice((id, homeoffice)) => action_handler
+            .update_block_homeoffice(id, homeoffice, token)
+            .handle_response(Msg::BlockUpdated, Msg::UpdateFailed)
+            .is_some(),
+        Command::BlockProject((id, project)) => {
+            known_block(id, token, action_handler) && annotations::set_project(id, &project)
+        }
+        Command::BlockTag((id, tag)) => {
+            known_block(id, token, action_handler) && annotations::add_tag(id, &tag)
+        }
+        Command::BlockNote((id, note)) => {
+            known_block(id, token, action_handler) && annotations::set_block_note(id, &note)
+        }
+        Command::BlockSearch(text) => {
+            let blocks = action_handler
+                .get_all_blocks(token)
+                .handle_response(Msg::SearchResults(&text), Msg::NoBlocks);
+
+            let Some(blocks) = blocks else {
+                return false;
+            };
+            let found: Vec<&Block> = blocks.iter().filter(|block| block.matches(&text)).collect();
+            if found.is_empty() {
+                println!("{}", Msg::NothingFound);
+            }
+            for block in found {
+                block.display(&config.display);
+                println!();
+            }
+            true
+        }
+        Command::PauseNote((id, note)) => {
+            known_pause(id, token, action_handler) && annotations::set_pause_note(id, &note)
+        }
+        Command::PauseDelete((id, yes)) => {
+            confirm_pause_delete(id, yes, token, config, action_handler)
+                && action_handler
+                    .delete_pause(id, token)
+                    .handle_response(Msg::PauseDeleted, Msg::PauseNotFound)
+                    .is_some()
+        }
+        Command::PauseUpdateStart((id, start)) => action_handler
+            .update_pause_start(id, &start, token)
+            .handle_response(Msg::PauseUpdated, Msg::UpdateFailed)
+            .is_some(),
+        Command::PauseUpdateEnd((id, end)) => action_handler
+            .update_pause_end(id, &end, token)
+            .handle_response(Msg::PauseUpdated, Msg::UpdateFailed)
+            .is_some(),
+        Command::Status(format) => {
+            status::print_status(format.as_deref(), config, token, action_handler)
+        }
+        Command::Feierabend => projection::print_projection(token, config, action_handler),
+        Command::Dashboard => dashboard::run(token, config, action_handler),
+        Command::Report((month, exact)) => {
+            report::print_report(month, exact, token, config, action_handler)
+        }
+        Command::Tax((year, csv, exact)) => {
+            tax::print_tax(year, csv, exact, token, config, action_handler)
+        }
+        Command::Export((format, path, exact)) => export::export(
+            format,
+            path.as_deref(),
+            exact,
+            token,
+            config,
+            action_handler,
+        ),
+        Command::Projects((month, exact)) => {
+            projects::print_projects(month, exact, token, config, action_handler)
+        }
+        Command::Earnings((month, exact)) => {
+            earnings::print_earnings(month, exact, token, config, action_handler)
+        }
+        Command::Homeoffice((month, exact)) => {
+            homeoffice::print_homeoffice(month, exact, token, config, action_handler)
+        }
+        Command::Undo => journal::undo(token, action_handler),
+        command => execute_offline(command, config),
+    }
+}
+
+/// Whether the server knows a block with the ID, so that nothing is stored
+/// locally for a mistyped one.
+fn known_block(id: i32, token: &Token, action_handler: &mut ActionHandler) -> bool {
+    let (blocks, message) = action_handler
+        .get_blocks_with_current(token)
+        .response_message(Msg::AllBlocks, Msg::BlockNotFound);
+
+    if blocks.is_some_and(|blocks| blocks.iter().any(|block| block.id == id)) {
+        return true;
+    }
+    match message == Msg::NetworkError.to_string() {
+        true => println!("{}", error_text(&message)),
+        false => println!("{}", error_text(&Msg::BlockNotFound.to_string())),
+    }
+    false
+}
+
+/// Whether the server knows a pause with the ID.
+fn known_pause(id: i32, token: &Token, action_handler: &mut ActionHandler) -> bool {
+    let (blocks, message) = action_handler
+        .get_blocks_with_current(token)
+        .response_message(Msg::AllBlocks, Msg::PauseNotFound);
+
+    let known = blocks.is_some_and(|blocks| {
+        blocks
+            .iter()
+            .any(|block| block.pauses().iter().any(|pause| pause.id == id))
+    });
+    if known {
+        return true;
+    }
+    match message == Msg::NetworkError.to_string() {
+        true => println!("{}", error_text(&message)),
+        false => println!("{}", error_text(&Msg::PauseNotFound.to_string())),
+    }
+    false
+}
+
+/// Shows the block and asks before deleting it, unless `--yes` was given or
+/// there is no terminal to ask.
+fn confirm_block_delete(
+    id: i32,
+    yes: bool,
+    token: &Token,
+    config: &Config,
+    action_handler: &mut ActionHandler,
+) -> bool {
+    if yes || !is_interactive() {
+        return true;
+    }
+    if !known_block(id, token, action_handler) {
+        return false;
+    }
+
+    if let Ok((blocks, _)) = action_handler.get_blocks_with_current(token) {
+        if let Some(block) = blocks.iter().find(|block| block.id == id) {
+            block.display(&config.display);
+        }
+    }
+    confirmed(confirm(Msg::ConfirmDeleteBlock(id)))
+}
+
+/// Shows the pause and asks before deleting it, unless `--yes` was given or
+/// there is no terminal to ask.
+fn confirm_pause_delete(
+    id: i32,
+    yes: bool,
+    token: &Token,
+    config: &Config,
+    action_handler: &mut ActionHandler,
+) -> bool {
+    if yes || !is_interactive() {
+        return true;
+    }
+    if !known_pause(id, token, action_handler) {
+        return false;
+    }
+
+    if let Ok((blocks, _)) = action_handler.get_blocks_with_current(token) {
+        let pause = blocks
+            .iter()
+            .flat_map(|block| block.pauses())
+            .find(|pause| pause.id == id);
+        if let Some(pause) = pause {
+            pause.display(&config.display);
+        }
+    }
+    confirmed(confirm(Msg::ConfirmDeletePause(id)))
+}
+
+fn confirmed(confirmed: bool) -> bool {
+    if !confirmed {
+        println!("{}", Msg::DeleteCancelled);
+    }
+    confirmed
+}
+
+/// Runs a command that does not talk to the server and returns whether it
+/// was successful.
+fn execute_offline(command: Command, config: &Config) -> bool {
+    match command {
+        Command::AbsenceAdd((kind, from, to, half)) => absence::add(kind, from, to, half),
+        Command::AbsenceList => absence::print_list(config),
+        Command::AbsenceDelete(id) => absence::delete(id),
+        Command::Help(topic) => {
+            print_help(topic.as_deref(), config);
+            true
+        }
+        _ => true,
+    }
+}
+
+fn print_help(topic: Option<&str>, config: &Config) {
+    let help = parse::help(topic);
+    let width = help.iter().map(|(usage, _)| usage.len()).max().unwrap_or(0);
+
+    for (usage, description) in help {
+        println!("{usage:<width$}  {description}");
+    }
+
+    if topic.is_none() && !config.aliases.is_empty() {
+        let mut aliases: Vec<(&String, &String)> = config.aliases.iter().collect();
+        aliases.sort();
+
+        println!();
+        println!("{}", Msg::Aliases);
+        for (name, definition) in aliases {
+            println!("{name:<width$}  {definition}");
+        }
+    }
+}
+
+fn print_parse_error(err: &ParseError) {
+    println!("{}", error_text(&format!("> {err}")));
+
+    for usage in err.usage() {
+        println!("{}", Msg::Usage(usage));
+    }
+    if let ParseError::UnknownCommand { .. } = err {
+        println!("{}", Msg::HelpHint);
+    }
+}
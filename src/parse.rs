@@ -1,123 +1,1219 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{absence::Kind, error::ParseError, export::Format, i18n::Msg};
+
+const SEPARATOR: &str = ";";
+const MAX_ALIAS_DEPTH: usize = 10;
+
 pub enum Command {
-    BlockStart(bool),
+    BlockStart((bool, Option<String>)),
     BlockEnd,
-    BlockDelete(i32),
-    BlockCurrent,
+    BlockDelete((i32, bool)),
+    BlockCurrent(bool),
     BlockAll,
     BlockUpdateStart((i32, String)),
     BlockUpdateEnd((i32, String)),
     BlockUpdateHomeoffice((i32, bool)),
+    BlockProject((i32, String)),
+    BlockTag((i32, String)),
+    BlockNote((i32, String)),
+    BlockSearch(String),
     PauseStart,
     PauseEnd,
-    PauseDelete(i32),
+    PauseDelete((i32, bool)),
     PauseUpdateStart((i32, String)),
     PauseUpdateEnd((i32, String)),
+    PauseNote((i32, String)),
+    Status(Option<String>),
+    Dashboard,
+    Feierabend,
+    Report((Option<(i32, u32)>, bool)),
+    Homeoffice((Option<(i32, u32)>, bool)),
+    Tax((Option<i32>, bool, bool)),
+    Earnings((Option<(i32, u32)>, bool)),
+    Projects((Option<(i32, u32)>, bool)),
+    Export((Format, Option<String>, bool)),
+    AbsenceAdd((Kind, NaiveDate, NaiveDate, bool)),
+    AbsenceList,
+    AbsenceDelete(u32),
+    Undo,
+    Help(Option<String>),
     Exit,
-    Unknown,
 }
 
-pub fn parse_command(command: &str) -> Command {
-    let split: Vec<&str> = command.split(" ").collect();
-    match split[0] {
-        "block" => parse_block_command(&split),
-        "pause" => parse_pause_command(&split),
-        "exit" => Command::Exit,
-        _ => Command::Unknown,
+impl Command {
+    /// Whether the command talks to the server.
+    pub fn needs_login(&self) -> bool {
+        !matches!(
+            self,
+            Command::AbsenceAdd(_)
+                | Command::AbsenceList
+                | Command::AbsenceDelete(_)
+                | Command::Help(_)
+                | Command::Exit
+        )
+    }
+}
+
+pub enum Arg {
+    Keyword(&'static str),
+    BlockId,
+    PauseId,
+    AbsenceId,
+    Project,
+    Tag,
+    /// Free text, like a note.
+    Text,
+    File,
+    Bool,
+    Date,
+    Year,
+    /// A month as `YYYY-MM`.
+    Month,
+    /// A day or a range of days as `YYYY-MM-DD..YYYY-MM-DD`.
+    Days,
+    /// The first word of a command, used by `help`.
+    Topic,
+}
+
+pub struct Flag {
+    pub name: &'static str,
+    pub takes_value: bool,
+}
+
+pub struct CommandSpec {
+    pub words: &'static [&'static str],
+    pub args: &'static [Arg],
+    pub flags: &'static [Flag],
+    pub description: Msg<'static>,
+}
+
+const fn spec(
+    words: &'static [&'static str],
+    args: &'static [Arg],
+    description: Msg<'static>,
+) -> CommandSpec {
+    CommandSpec {
+        words,
+        args,
+        flags: &[],
+        description,
+    }
+}
+
+impl CommandSpec {
+    const fn with_flags(self, flags: &'static [Flag]) -> CommandSpec {
+        CommandSpec { flags, ..self }
+    }
+}
+
+/// A word of the input, quoted words are never treated as flags.
+pub struct Word {
+    pub text: String,
+    pub quoted: bool,
+}
+
+impl Word {
+    pub fn unquoted(text: &str) -> Word {
+        Word {
+            text: text.to_string(),
+            quoted: false,
+        }
+    }
+}
+
+/// The flags given to a command, with their values.
+#[derive(Default)]
+pub struct Flags(Vec<(String, Option<String>)>);
+
+impl Flags {
+    pub fn has(&self, name: &str) -> bool {
+        self.0.iter().any(|(flag, _)| flag == name)
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(flag, _)| flag == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+}
+
+/// Sets the project of the started block.
+const PROJECT_FLAG: Flag = Flag {
+    name: "project",
+    takes_value: true,
+};
+
+/// Takes only half of each day off.
+const HALF_FLAG: &[Flag] = &[Flag {
+    name: "half",
+    takes_value: false,
+}];
+
+/// Deletes without asking first.
+const YES_FLAG: &[Flag] = &[Flag {
+    name: "yes",
+    takes_value: false,
+}];
+
+/// Shows the working times without rounding.
+const EXACT_FLAG: &[Flag] = &[Flag {
+    name: "exact",
+    takes_value: false,
+}];
+
+/// `--csv` prints CSV instead of a table.
+const TAX_FLAGS: &[Flag] = &[
+    Flag {
+        name: "csv",
+        takes_value: false,
+    },
+    Flag {
+        name: "exact",
+        takes_value: false,
+    },
+];
+
+pub const COMMANDS: &[CommandSpec] = &[
+    spec(&["block", "start"], &[], Msg::HelpBlockStart).with_flags(&[
+        Flag {
+            name: "homeoffice",
+            takes_value: false,
+        },
+        PROJECT_FLAG,
+    ]),
+    spec(
+        &["block", "start"],
+        &[Arg::Bool],
+        Msg::HelpBlockStartHomeoffice,
+    )
+    .with_flags(&[PROJECT_FLAG]),
+    spec(&["block", "end"], &[], Msg::HelpBlockEnd),
+    spec(&["block", "delete"], &[Arg::BlockId], Msg::HelpBlockDelete).with_flags(YES_FLAG),
+    spec(&["block", "current"], &[], Msg::HelpBlockCurrent).with_flags(&[Flag {
+        name: "watch",
+        takes_value: false,
+    }]),
+    spec(&["block", "all"], &[], Msg::HelpBlockAll),
+    spec(
+        &["block", "update"],
+        &[Arg::BlockId, Arg::Keyword("start"), Arg::Date],
+        Msg::HelpBlockUpdateStart,
+    ),
+    spec(
+        &["block", "update"],
+        &[Arg::BlockId, Arg::Keyword("end"), Arg::Date],
+        Msg::HelpBlockUpdateEnd,
+    ),
+    spec(
+        &["block", "update"],
+        &[Arg::BlockId, Arg::Keyword("homeoffice"), Arg::Bool],
+        Msg::HelpBlockUpdateHomeoffice,
+    ),
+    spec(
+        &["block", "project"],
+        &[Arg::BlockId, Arg::Project],
+        Msg::HelpBlockProject,
+    ),
+    spec(
+        &["block", "tag"],
+        &[Arg::BlockId, Arg::Tag],
+        Msg::HelpBlockTag,
+    ),
+    spec(
+        &["block", "note"],
+        &[Arg::BlockId, Arg::Text],
+        Msg::HelpBlockNote,
+    ),
+    spec(&["block", "search"], &[Arg::Text], Msg::HelpBlockSearch),
+    spec(&["pause", "start"], &[], Msg::HelpPauseStart),
+    spec(&["pause", "end"], &[], Msg::HelpPauseEnd),
+    spec(&["pause", "delete"], &[Arg::PauseId], Msg::HelpPauseDelete).with_flags(YES_FLAG),
+    spec(
+        &["pause", "update"],
+        &[Arg::PauseId, Arg::Keyword("start"), Arg::Date],
+        Msg::HelpPauseUpdateStart,
+    ),
+    spec(
+        &["pause", "update"],
+        &[Arg::PauseId, Arg::Keyword("end"), Arg::Date],
+        Msg::HelpPauseUpdateEnd,
+    ),
+    spec(
+        &["pause", "note"],
+        &[Arg::PauseId, Arg::Text],
+        Msg::HelpPauseNote,
+    ),
+    spec(&["status"], &[], Msg::HelpStatus).with_flags(&[Flag {
+        name: "format",
+        takes_value: true,
+    }]),
+    spec(&["feierabend"], &[], Msg::HelpFeierabend),
+    spec(&["dashboard"], &[], Msg::HelpDashboard),
+    spec(&["report"], &[], Msg::HelpReport).with_flags(EXACT_FLAG),
+    spec(&["report"], &[Arg::Month], Msg::HelpReportMonth).with_flags(EXACT_FLAG),
+    spec(&["homeoffice"], &[], Msg::HelpHomeoffice).with_flags(EXACT_FLAG),
+    spec(&["homeoffice"], &[Arg::Month], Msg::HelpHomeofficeMonth).with_flags(EXACT_FLAG),
+    spec(&["projects"], &[], Msg::HelpProjects).with_flags(EXACT_FLAG),
+    spec(&["projects"], &[Arg::Month], Msg::HelpProjectsMonth).with_flags(EXACT_FLAG),
+    spec(&["export", "csv"], &[], Msg::HelpExportCsv).with_flags(EXACT_FLAG),
+    spec(&["export", "csv"], &[Arg::File], Msg::HelpExportCsvFile).with_flags(EXACT_FLAG),
+    spec(&["export", "json"], &[], Msg::HelpExportJson).with_flags(EXACT_FLAG),
+    spec(&["export", "json"], &[Arg::File], Msg::HelpExportJsonFile).with_flags(EXACT_FLAG),
+    spec(&["earnings"], &[], Msg::HelpEarnings).with_flags(EXACT_FLAG),
+    spec(&["earnings"], &[Arg::Month], Msg::HelpEarningsMonth).with_flags(EXACT_FLAG),
+    spec(&["tax"], &[], Msg::HelpTax).with_flags(TAX_FLAGS),
+    spec(&["tax"], &[Arg::Year], Msg::HelpTaxYear).with_flags(TAX_FLAGS),
+    spec(
+        &["absence", "add"],
+        &[Arg::Keyword("vacation"), Arg::Days],
+        Msg::HelpAbsenceAddVacation,
+    )
+    .with_flags(HALF_FLAG),
+    spec(
+        &["absence", "add"],
+        &[Arg::Keyword("sick"), Arg::Days],
+        Msg::HelpAbsenceAddSick,
+    )
+    .with_flags(HALF_FLAG),
+    spec(
+        &["absence", "add"],
+        &[Arg::Keyword("comp"), Arg::Days],
+        Msg::HelpAbsenceAddComp,
+    )
+    .with_flags(HALF_FLAG),
+    spec(&["absence", "list"], &[], Msg::HelpAbsenceList),
+    spec(
+        &["absence", "delete"],
+        &[Arg::AbsenceId],
+        Msg::HelpAbsenceDelete,
+    ),
+    spec(&["undo"], &[], Msg::HelpUndo),
+    spec(&["help"], &[], Msg::HelpHelp),
+    spec(&["help"], &[Arg::Topic], Msg::HelpHelpTopic),
+    spec(&["exit"], &[], Msg::HelpExit),
+];
+
+impl CommandSpec {
+    /// Whether the tokens are a valid beginning of this command.
+    pub fn matches_prefix(&self, tokens: &[&str]) -> bool {
+        tokens.len() <= self.words.len() + self.args.len()
+            && tokens
+                .iter()
+                .enumerate()
+                .all(|(i, token)| match self.words.get(i) {
+                    Some(word) => word == token,
+                    None => self.args[i - self.words.len()].accepts(token),
+                })
+    }
+
+    pub fn matches(&self, tokens: &[&str]) -> bool {
+        tokens.len() == self.words.len() + self.args.len() && self.matches_prefix(tokens)
+    }
+
+    /// Number of leading tokens accepted by this command.
+    fn matched_len(&self, tokens: &[&str]) -> usize {
+        (0..=tokens.len())
+            .rev()
+            .find(|len| self.matches_prefix(&tokens[..*len]))
+            .unwrap_or(0)
+    }
+
+    pub fn usage(&self) -> String {
+        let mut usage: Vec<String> = self.words.iter().map(|word| word.to_string()).collect();
+        usage.extend(self.args.iter().map(Arg::name));
+        usage.extend(self.flags.iter().map(|flag| match flag.takes_value {
+            true => format!("[--{} {}]", flag.name, Msg::ArgValue),
+            false => format!("[--{}]", flag.name),
+        }));
+        usage.join(" ")
+    }
+}
+
+impl Arg {
+    fn accepts(&self, token: &str) -> bool {
+        match self {
+            Arg::Keyword(keyword) => *keyword == token,
+            Arg::BlockId | Arg::PauseId => token.parse::<i32>().is_ok(),
+            Arg::AbsenceId => token.parse::<u32>().is_ok(),
+            Arg::Project | Arg::Tag | Arg::File => !token.is_empty(),
+            Arg::Text => true,
+            Arg::Bool => token.parse::<bool>().is_ok(),
+            Arg::Date => true,
+            Arg::Year => token.len() == 4 && token.parse::<i32>().is_ok(),
+            Arg::Month => parse_month(token).is_some(),
+            Arg::Days => parse_days(token).is_some(),
+            Arg::Topic => topics().contains(&token),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Arg::Keyword(keyword) => keyword.to_string(),
+            Arg::BlockId => String::from("{block-id}"),
+            Arg::PauseId => String::from("{pause-id}"),
+            Arg::AbsenceId => String::from("{absence-id}"),
+            Arg::Project => Msg::ArgProject.to_string(),
+            Arg::Tag => Msg::ArgTag.to_string(),
+            Arg::Text => Msg::ArgText.to_string(),
+            Arg::File => Msg::ArgFile.to_string(),
+            Arg::Bool => String::from("{true/false}"),
+            Arg::Date => Msg::ArgDate.to_string(),
+            Arg::Year => Msg::ArgYear.to_string(),
+            Arg::Month => Msg::ArgMonth.to_string(),
+            Arg::Days => Msg::ArgDays.to_string(),
+            Arg::Topic => Msg::ArgTopic.to_string(),
+        }
+    }
+}
+
+/// Parses a month written as `YYYY-MM` into year and month.
+fn parse_month(token: &str) -> Option<(i32, u32)> {
+    let date = NaiveDate::parse_from_str(&format!("{token}-01"), "%Y-%m-%d").ok()?;
+    Some((date.year(), date.month()))
+}
+
+/// Parses a day or an inclusive range of days, both written as `YYYY-MM-DD`.
+fn parse_days(token: &str) -> Option<(NaiveDate, NaiveDate)> {
+    let (from, to) = token.split_once("..").unwrap_or((token, token));
+    let from = NaiveDate::parse_from_str(from, "%Y-%m-%d").ok()?;
+    let to = NaiveDate::parse_from_str(to, "%Y-%m-%d").ok()?;
+    (from <= to).then_some((from, to))
+}
+
+/// The distinct first words of all commands.
+pub fn topics() -> Vec<&'static str> {
+    let mut topics: Vec<&str> = Vec::new();
+    for spec in COMMANDS {
+        if !topics.contains(&spec.words[0]) {
+            topics.push(spec.words[0]);
+        }
     }
+    topics
 }
 
-fn parse_block_command(split: &Vec<&str>) -> Command {
-    let len = split.len();
+/// Returns the usage lines and descriptions of all commands for a topic.
+pub fn help(topic: Option<&str>) -> Vec<(String, String)> {
+    COMMANDS
+        .iter()
+        .filter(|spec| topic.is_none_or(|topic| spec.words[0] == topic))
+        .map(|spec| (spec.usage(), spec.description.to_string()))
+        .collect()
+}
+
+/// Parses a line of one or more `;`-separated commands, expanding aliases.
+pub fn parse_line(
+    line: &str,
+    aliases: &HashMap<String, String>,
+) -> Result<Vec<Command>, ParseError> {
+    parse_words(tokenize(line)?, aliases)
+}
+
+pub fn parse_words(
+    words: Vec<Word>,
+    aliases: &HashMap<String, String>,
+) -> Result<Vec<Command>, ParseError> {
+    expand_aliases(words, aliases, &[])?
+        .into_iter()
+        .filter(|words| !words.is_empty())
+        .map(parse_tokens)
+        .collect()
+}
+
+/// Splits the words into commands and replaces aliases by their definition,
+/// arguments following an alias are appended to its last command. Aliases
+/// take precedence over commands of the same name, which an alias can still
+/// use in its own definition.
+fn expand_aliases(
+    words: Vec<Word>,
+    aliases: &HashMap<String, String>,
+    expanding: &[&str],
+) -> Result<Vec<Vec<Word>>, ParseError> {
+    let mut commands = Vec::new();
+    // The command an alias of the same name is defined with.
+    let own_command = |text: &str| expanding.contains(&text) && topics().contains(&text);
 
-    if len == 1 {
-        return Command::Unknown;
+    for command in split_commands(words) {
+        let alias = command
+            .first()
+            .filter(|word| !word.quoted && !own_command(&word.text))
+            .and_then(|word| Some((word.text.clone(), aliases.get(&word.text)?)));
+
+        let Some((name, alias)) = alias else {
+            commands.push(command);
+            continue;
+        };
+
+        if expanding.len() >= MAX_ALIAS_DEPTH {
+            return Err(ParseError::AliasLoop(name));
+        }
+
+        let mut expansion = tokenize(alias)?;
+        expansion.extend(command.into_iter().skip(1));
+        let expanding = [expanding, &[name.as_str()]].concat();
+        commands.extend(expand_aliases(expansion, aliases, &expanding)?);
     }
 
-    match split[1] {
-        "start" => {
-            if len < 3 {
-                return Command::BlockStart(false);
+    Ok(commands)
+}
+
+/// Splits the words at unquoted `;` into the words of each command.
+pub fn split_commands(words: Vec<Word>) -> Vec<Vec<Word>> {
+    let mut commands = vec![Vec::new()];
+
+    for word in words {
+        if !word.quoted && word.text == SEPARATOR {
+            commands.push(Vec::new());
+        } else if let Some(command) = commands.last_mut() {
+            command.push(word);
+        }
+    }
+
+    commands
+}
+
+fn parse_tokens(tokens: Vec<Word>) -> Result<Command, ParseError> {
+    let (words, flags) = split_flags(tokens)?;
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+
+    let Some(spec) = COMMANDS.iter().find(|spec| spec.matches(&words)) else {
+        return Err(parse_error(&words));
+    };
+
+    for (name, _) in &flags.0 {
+        if !spec.flags.iter().any(|flag| flag.name == name) {
+            let names: Vec<&str> = spec.flags.iter().map(|flag| flag.name).collect();
+            return Err(ParseError::UnknownFlag {
+                flag: name.to_string(),
+                suggestion: suggest(name, &names),
+                usage: vec![spec.usage()],
+            });
+        }
+    }
+
+    build_command(spec.words, &words[spec.words.len()..], &flags).ok_or_else(|| parse_error(&words))
+}
+
+/// Splits the input into words, honoring single and double quotes as well as
+/// backslash escapes.
+pub fn tokenize(input: &str) -> Result<Vec<Word>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut current: Option<Word> = None;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if let Some(token) = current.take() {
+                    tokens.push(token);
+                }
             }
-            let homeoffice = split[2].trim().parse::<bool>();
-            match homeoffice {
-                Ok(homeoffice) => Command::BlockStart(homeoffice),
-                Err(_) => Command::BlockStart(false),
+            '\'' | '"' => {
+                let token = current.get_or_insert_with(|| Word::unquoted(""));
+                token.quoted = true;
+                loop {
+                    match chars.next() {
+                        Some(end) if end == c => break,
+                        Some('\\') if c == '"' => match chars.next() {
+                            Some(escaped) => token.text.push(escaped),
+                            None => return Err(ParseError::UnterminatedQuote),
+                        },
+                        Some(other) => token.text.push(other),
+                        None => return Err(ParseError::UnterminatedQuote),
+                    }
+                }
             }
-        }
-        "end" => Command::BlockEnd,
-        "delete" => {
-            if len < 3 {
-                return Command::Unknown;
+            '\\' => {
+                let token = current.get_or_insert_with(|| Word::unquoted(""));
+                token.quoted = true;
+                token.text.push(chars.next().unwrap_or('\\'));
             }
-            let id = split[2].trim().parse::<i32>();
-            match id {
-                Ok(id) => Command::BlockDelete(id),
-                Err(_) => Command::Unknown,
+            ';' => {
+                tokens.extend(current.take());
+                tokens.push(Word::unquoted(SEPARATOR));
             }
+            c => current
+                .get_or_insert_with(|| Word::unquoted(""))
+                .text
+                .push(c),
         }
-        "current" => Command::BlockCurrent,
-        "all" => Command::BlockAll,
-        "update" => parse_block_update_command(split),
-        _ => Command::Unknown,
     }
+
+    if let Some(token) = current {
+        tokens.push(token);
+    }
+
+    Ok(tokens)
 }
 
-fn parse_block_update_command(split: &Vec<&str>) -> Command {
-    if split.len() < 5 {
-        return Command::Unknown;
+/// Separates `--flag value` and `--flag=value` options from the words.
+fn split_flags(tokens: Vec<Word>) -> Result<(Vec<String>, Flags), ParseError> {
+    let mut words = Vec::new();
+    let mut flags = Flags::default();
+    let mut tokens = tokens.into_iter();
+
+    while let Some(token) = tokens.next() {
+        let name = match token.text.strip_prefix("--") {
+            Some(name) if !token.quoted && !name.is_empty() => name,
+            _ => {
+                words.push(token.text);
+                continue;
+            }
+        };
+
+        let (name, value) = match name.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (name, None),
+        };
+
+        let Some(flag) = find_flag(name) else {
+            return Err(ParseError::UnknownFlag {
+                flag: name.to_string(),
+                suggestion: suggest(name, &flag_names()),
+                usage: Vec::new(),
+            });
+        };
+
+        let value = match (flag.takes_value, value) {
+            (true, Some(value)) => Some(value),
+            (true, None) => match tokens.next() {
+                Some(value) => Some(value.text),
+                None => return Err(ParseError::MissingFlagValue(flag.name.to_string())),
+            },
+            (false, Some(_)) => return Err(ParseError::UnexpectedFlagValue(flag.name.to_string())),
+            (false, None) => None,
+        };
+
+        flags.0.push((flag.name.to_string(), value));
     }
 
-    let id = split[2].trim().parse::<i32>();
-    match id {
-        Ok(id) => match split[3] {
-            "start" => Command::BlockUpdateStart((id, split[4].to_string())),
-            "end" => Command::BlockUpdateEnd((id, split[4].to_string())),
-            "homeoffice" => {
-                let homeoffice = split[4].trim().parse::<bool>();
-                match homeoffice {
-                    Ok(homeoffice) => Command::BlockUpdateHomeoffice((id, homeoffice)),
-                    Err(_) => Command::Unknown,
+    Ok((words, flags))
+}
+
+/// Leniently drops flags and their values, for completing partial input.
+pub fn positional_words(words: &[Word]) -> Vec<&str> {
+    let mut positional = Vec::new();
+    let mut words = words.iter();
+
+    while let Some(word) = words.next() {
+        match word.text.strip_prefix("--") {
+            Some(name) if !word.quoted => {
+                if find_flag(name).is_some_and(|flag| flag.takes_value) {
+                    words.next();
                 }
             }
-            _ => Command::Unknown,
-        },
-        Err(_) => Command::Unknown,
+            _ => positional.push(word.text.as_str()),
+        }
     }
+
+    positional
 }
 
-fn parse_pause_command(split: &Vec<&str>) -> Command {
-    let len = split.len();
+fn find_flag(name: &str) -> Option<&'static Flag> {
+    COMMANDS
+        .iter()
+        .flat_map(|spec| spec.flags)
+        .find(|flag| flag.name == name)
+}
 
-    match split[1] {
-        "start" => Command::PauseStart,
-        "end" => Command::PauseEnd,
-        "delete" => {
-            if len < 3 {
-                return Command::Unknown;
-            }
-            let id = split[2].trim().parse::<i32>();
-            match id {
-                Ok(id) => Command::PauseDelete(id),
-                Err(_) => Command::Unknown,
+fn flag_names() -> Vec<&'static str> {
+    COMMANDS
+        .iter()
+        .flat_map(|spec| spec.flags)
+        .map(|flag| flag.name)
+        .collect()
+}
+
+/// Finds the commands that come closest to the tokens and describes why
+/// none of them matched.
+fn parse_error(tokens: &[&str]) -> ParseError {
+    let best_len = COMMANDS
+        .iter()
+        .map(|spec| spec.matched_len(tokens))
+        .max()
+        .unwrap_or(0);
+
+    // Only flags were given.
+    let Some(first) = tokens.first() else {
+        return ParseError::MissingArgument {
+            name: topics().join("/"),
+            usage: Vec::new(),
+        };
+    };
+
+    if best_len == 0 {
+        return ParseError::UnknownCommand {
+            command: first.to_string(),
+            suggestion: suggest(first, &topics()),
+        };
+    }
+
+    let candidates: Vec<&CommandSpec> = COMMANDS
+        .iter()
+        .filter(|spec| spec.matched_len(tokens) == best_len)
+        .collect();
+    let usage = candidates.iter().map(|spec| spec.usage()).collect();
+
+    let Some(token) = tokens.get(best_len) else {
+        let arg_names: Vec<String> = candidates
+            .iter()
+            .filter_map(|spec| nth_arg_name(spec, best_len))
+            .collect();
+        return ParseError::MissingArgument {
+            name: join_unique(&arg_names),
+            usage,
+        };
+    };
+
+    let allowed: Vec<String> = candidates
+        .iter()
+        .filter_map(|spec| nth_arg_name(spec, best_len))
+        .collect();
+    let expected = candidates.iter().find_map(|spec| nth_arg(spec, best_len));
+
+    match expected {
+        _ if allowed.is_empty() => ParseError::TooManyArguments { usage },
+        Some(Arg::BlockId | Arg::PauseId | Arg::AbsenceId) => ParseError::InvalidId {
+            value: token.to_string(),
+            usage,
+        },
+        Some(Arg::Bool) => ParseError::InvalidBool {
+            value: token.to_string(),
+            usage,
+        },
+        Some(Arg::Topic) => ParseError::UnknownCommand {
+            command: token.to_string(),
+            suggestion: suggest(token, &topics()),
+        },
+        _ => {
+            let allowed_refs: Vec<&str> = allowed.iter().map(String::as_str).collect();
+            let suggestion = suggest(token, &allowed_refs);
+            let usage = match suggestion {
+                Some(ref suggestion) => candidates
+                    .iter()
+                    .filter(|spec| nth_arg_name(spec, best_len).as_ref() == Some(suggestion))
+                    .map(|spec| spec.usage())
+                    .collect(),
+                None => usage,
+            };
+            ParseError::UnknownVerb {
+                value: token.to_string(),
+                allowed: join_unique(&allowed),
+                suggestion,
+                usage,
             }
         }
-        "update" => parse_pause_update_command(split),
-        _ => Command::Unknown,
     }
 }
 
-fn parse_pause_update_command(split: &Vec<&str>) -> Command {
-    if split.len() < 5 {
-        return Command::Unknown;
+/// The word or argument a command expects at the given token position.
+fn nth_arg(spec: &CommandSpec, position: usize) -> Option<&Arg> {
+    position
+        .checked_sub(spec.words.len())
+        .and_then(|i| spec.args.get(i))
+}
+
+fn nth_arg_name(spec: &CommandSpec, position: usize) -> Option<String> {
+    match spec.words.get(position) {
+        Some(word) => Some(word.to_string()),
+        None => nth_arg(spec, position).map(Arg::name),
     }
+}
 
-    let id = split[2].trim().parse::<i32>();
-    match id {
-        Ok(id) => match split[3] {
-            "start" => Command::PauseUpdateStart((id, split[4].to_string())),
-            "end" => Command::PauseUpdateEnd((id, split[4].to_string())),
-            _ => Command::Unknown,
-        },
-        Err(_) => Command::Unknown,
+fn join_unique(names: &[String]) -> String {
+    let mut unique: Vec<&str> = Vec::new();
+    for name in names {
+        if !unique.contains(&name.as_str()) {
+            unique.push(name);
+        }
+    }
+    unique.join("/")
+}
+
+/// Returns the option closest to the input if it looks like a typo.
+fn suggest(input: &str, options: &[&str]) -> Option<String> {
+    options
+        .iter()
+        .map(|option| (levenshtein(input, option), option))
+        .filter(|(distance, option)| *distance <= 2.max(option.len() / 3))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, option)| option.to_string())
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let cost = if a == *b { 0 } else { 1 };
+            current.push(
+                (previous[j] + cost)
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+fn build_command(words: &[&str], args: &[&str], flags: &Flags) -> Option<Command> {
+    let project = flags.value("project").map(str::to_string);
+    let command = match (words, args) {
+        (["block", "start"], []) => Command::BlockStart((flags.has("homeoffice"), project)),
+        (["block", "start"], [homeoffice]) => {
+            Command::BlockStart((homeoffice.parse().ok()?, project))
+        }
+        (["block", "end"], []) => Command::BlockEnd,
+        (["block", "delete"], [id]) => Command::BlockDelete((id.parse().ok()?, flags.has("yes"))),
+        (["block", "current"], []) => Command::BlockCurrent(flags.has("watch")),
+        (["block", "all"], []) => Command::BlockAll,
+        (["block", "update"], [id, "start", start]) => {
+            Command::BlockUpdateStart((id.parse().ok()?, start.to_string()))
+        }
+        (["block", "update"], [id, "end", end]) => {
+            Command::BlockUpdateEnd((id.parse().ok()?, end.to_string()))
+        }
+        (["block", "update"], [id, "homeoffice", homeoffice]) => {
+            Command::BlockUpdateHomeoffice((id.parse().ok()?, homeoffice.parse().ok()?))
+        }
+        (["block", "project"], [id, project]) => {
+            Command::BlockProject((id.parse().ok()?, project.to_string()))
+        }
+        (["block", "tag"], [id, tag]) => Command::BlockTag((id.parse().ok()?, tag.to_string())),
+        (["block", "note"], [id, note]) => Command::BlockNote((id.parse().ok()?, note.to_string())),
+        (["block", "search"], [text]) => Command::BlockSearch(text.to_string()),
+        (["pause", "start"], []) => Command::PauseStart,
+        (["pause", "end"], []) => Command::PauseEnd,
+        (["pause", "delete"], [id]) => Command::PauseDelete((id.parse().ok()?, flags.has("yes"))),
+        (["pause", "update"], [id, "start", start]) => {
+            Command::PauseUpdateStart((id.parse().ok()?, start.to_string()))
+        }
+        (["pause", "update"], [id, "end", end]) => {
+            Command::PauseUpdateEnd((id.parse().ok()?, end.to_string()))
+        }
+        (["pause", "note"], [id, note]) => Command::PauseNote((id.parse().ok()?, note.to_string())),
+        (["status"], []) => Command::Status(flags.value("format").map(str::to_string)),
+        (["feierabend"], []) => Command::Feierabend,
+        (["dashboard"], []) => Command::Dashboard,
+        (["report"], []) => Command::Report((None, flags.has("exact"))),
+        (["report"], [month]) => Command::Report((Some(parse_month(month)?), flags.has("exact"))),
+        (["projects"], []) => Command::Projects((None, flags.has("exact"))),
+        (["projects"], [month]) => {
+            Command::Projects((Some(parse_month(month)?), flags.has("exact")))
+        }
+        (["export", format], file) if file.len() <= 1 => {
+            let format = match *format {
+                "csv" => Format::Csv,
+                "json" => Format::Json,
+                _ => return None,
+            };
+            Command::Export((
+                format,
+                file.first().map(|file| file.to_string()),
+                flags.has("exact"),
+            ))
+        }
+        (["earnings"], []) => Command::Earnings((None, flags.has("exact"))),
+        (["earnings"], [month]) => {
+            Command::Earnings((Some(parse_month(month)?), flags.has("exact")))
+        }
+        (["tax"], []) => Command::Tax((None, flags.has("csv"), flags.has("exact"))),
+        (["tax"], [year]) => Command::Tax((
+            Some(year.parse().ok()?),
+            flags.has("csv"),
+            flags.has("exact"),
+        )),
+        (["homeoffice"], []) => Command::Homeoffice((None, flags.has("exact"))),
+        (["homeoffice"], [month]) => {
+            Command::Homeoffice((Some(parse_month(month)?), flags.has("exact")))
+        }
+        (["absence", "add"], [kind, days]) => {
+            let kind = match *kind {
+                "vacation" => Kind::Vacation,
+                "sick" => Kind::Sick,
+                "comp" => Kind::Comp,
+                _ => return None,
+            };
+            let (from, to) = parse_days(days)?;
+            Command::AbsenceAdd((kind, from, to, flags.has("half")))
+        }
+        (["absence", "list"], []) => Command::AbsenceList,
+        (["absence", "delete"], [id]) => Command::AbsenceDelete(id.parse().ok()?),
+        (["undo"], []) => Command::Undo,
+        (["help"], []) => Command::Help(None),
+        (["help"], [topic]) => Command::Help(Some(topic.to_string())),
+        (["exit"], []) => Command::Exit,
+        _ => return None,
+    };
+
+    Some(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Command {
+        let mut commands =
+            parse_line(line, &HashMap::new()).unwrap_or_else(|_| panic!("'{line}' should parse"));
+        assert_eq!(commands.len(), 1, "'{line}' should be one command");
+        commands.remove(0)
+    }
+
+    fn parse_err(line: &str) -> ParseError {
+        match parse_line(line, &HashMap::new()) {
+            Ok(_) => panic!("'{line}' should not parse"),
+            Err(err) => err,
+        }
+    }
+
+    fn texts(input: &str) -> Vec<String> {
+        let words = tokenize(input).unwrap_or_else(|_| panic!("'{input}' should tokenize"));
+        words.into_iter().map(|word| word.text).collect()
+    }
+
+    #[test]
+    fn tokenize_splits_on_any_whitespace() {
+        assert_eq!(texts("block  delete\t3 "), ["block", "delete", "3"]);
+        assert_eq!(texts("\tpause\t\tend"), ["pause", "end"]);
+        assert!(texts("   ").is_empty());
+    }
+
+    #[test]
+    fn tokenize_honors_quotes_and_escapes() {
+        assert_eq!(texts("note 1 \"a b\""), ["note", "1", "a b"]);
+        assert_eq!(texts("note 1 'a \\ b'"), ["note", "1", "a \\ b"]);
+        assert_eq!(
+            texts("note 1 \"say \\\"hi\\\"\""),
+            ["note", "1", "say \"hi\""]
+        );
+        assert_eq!(texts("a\\ b"), ["a b"]);
+        assert_eq!(texts("''"), [""]);
+        assert!(matches!(
+            tokenize("note \"open"),
+            Err(ParseError::UnterminatedQuote)
+        ));
+    }
+
+    #[test]
+    fn tokenize_separates_commands() {
+        assert_eq!(
+            texts("pause end;block end"),
+            ["pause", "end", ";", "block", "end"]
+        );
+        assert_eq!(texts("note 1 'a;b'"), ["note", "1", "a;b"]);
+
+        let commands = parse_line("pause end; block end", &HashMap::new())
+            .unwrap_or_else(|_| panic!("should parse"));
+        assert!(matches!(
+            commands.as_slice(),
+            [Command::PauseEnd, Command::BlockEnd]
+        ));
+    }
+
+    #[test]
+    fn quoted_separators_and_flags_are_words() {
+        assert!(matches!(
+            parse("block note 1 ';'"),
+            Command::BlockNote((1, ref note)) if note == ";"
+        ));
+        assert!(matches!(
+            parse("block note 1 '--yes'"),
+            Command::BlockNote((1, ref note)) if note == "--yes"
+        ));
+    }
+
+    #[test]
+    fn flags_take_values_with_space_or_equals() {
+        assert!(matches!(
+            parse("status --format '{time}'"),
+            Command::Status(Some(ref format)) if format == "{time}"
+        ));
+        assert!(matches!(
+            parse("status --format={time}"),
+            Command::Status(Some(ref format)) if format == "{time}"
+        ));
+        assert!(matches!(
+            parse("block start --project Alpha --homeoffice"),
+            Command::BlockStart((true, Some(ref project))) if project == "Alpha"
+        ));
+        assert!(matches!(
+            parse("block start --project=Alpha"),
+            Command::BlockStart((false, Some(ref project))) if project == "Alpha"
+        ));
+    }
+
+    #[test]
+    fn flag_errors() {
+        assert!(matches!(
+            parse_err("status --format"),
+            ParseError::MissingFlagValue(ref flag) if flag == "format"
+        ));
+        assert!(matches!(
+            parse_err("report --exact=yes"),
+            ParseError::UnexpectedFlagValue(ref flag) if flag == "exact"
+        ));
+        assert!(matches!(
+            parse_err("report --exakt"),
+            ParseError::UnknownFlag { ref suggestion, .. } if suggestion.as_deref() == Some("exact")
+        ));
+        assert!(matches!(
+            parse_err("block end --exact"),
+            ParseError::UnknownFlag { ref flag, .. } if flag == "exact"
+        ));
+    }
+
+    #[test]
+    fn only_flags_are_an_error() {
+        assert!(matches!(
+            parse_err("--yes"),
+            ParseError::MissingArgument { .. }
+        ));
+        assert!(matches!(
+            parse_err("help ; --yes"),
+            ParseError::MissingArgument { .. }
+        ));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(
+            parse_err("blok end"),
+            ParseError::UnknownCommand { ref suggestion, .. } if suggestion.as_deref() == Some("block")
+        ));
+        assert!(matches!(
+            parse_err("block delete x"),
+            ParseError::InvalidId { ref value, .. } if value == "x"
+        ));
+        assert!(matches!(
+            parse_err("block start maybe"),
+            ParseError::InvalidBool { .. }
+        ));
+        assert!(matches!(
+            parse_err("block delete"),
+            ParseError::MissingArgument { .. }
+        ));
+        assert!(matches!(
+            parse_err("block end now"),
+            ParseError::TooManyArguments { .. }
+        ));
+        assert!(matches!(
+            parse_err("block strat"),
+            ParseError::UnknownVerb { ref suggestion, .. } if suggestion.as_deref() == Some("start")
+        ));
+    }
+
+    #[test]
+    fn block_commands() {
+        assert!(matches!(
+            parse("block start"),
+            Command::BlockStart((false, None))
+        ));
+        assert!(matches!(
+            parse("block start true"),
+            Command::BlockStart((true, None))
+        ));
+        assert!(matches!(parse("block end"), Command::BlockEnd));
+        assert!(matches!(
+            parse("block delete 3"),
+            Command::BlockDelete((3, false))
+        ));
+        assert!(matches!(
+            parse("block delete 3 --yes"),
+            Command::BlockDelete((3, true))
+        ));
+        assert!(matches!(
+            parse("block current"),
+            Command::BlockCurrent(false)
+        ));
+        assert!(matches!(
+            parse("block current --watch"),
+            Command::BlockCurrent(true)
+        ));
+        assert!(matches!(parse("block all"), Command::BlockAll));
+        assert!(matches!(
+            parse("block update 2 start 2026-10-19T08:00:00+02:00"),
+            Command::BlockUpdateStart((2, ref start)) if start == "2026-10-19T08:00:00+02:00"
+        ));
+        assert!(matches!(
+            parse("block update 2 end 2026-10-19T17:00:00+02:00"),
+            Command::BlockUpdateEnd((2, ref end)) if end == "2026-10-19T17:00:00+02:00"
+        ));
+        assert!(matches!(
+            parse("block update 2 homeoffice true"),
+            Command::BlockUpdateHomeoffice((2, true))
+        ));
+        assert!(matches!(
+            parse("block project 2 'Kunde A'"),
+            Command::BlockProject((2, ref project)) if project == "Kunde A"
+        ));
+        assert!(matches!(
+            parse("block tag 2 meeting"),
+            Command::BlockTag((2, ref tag)) if tag == "meeting"
+        ));
+        assert!(matches!(
+            parse("block note 2 \"Notiz, lang\""),
+            Command::BlockNote((2, ref note)) if note == "Notiz, lang"
+        ));
+        assert!(matches!(
+            parse("block search kunde"),
+            Command::BlockSearch(ref text) if text == "kunde"
+        ));
+    }
+
+    #[test]
+    fn pause_commands() {
+        assert!(matches!(parse("pause start"), Command::PauseStart));
+        assert!(matches!(parse("pause end"), Command::PauseEnd));
+        assert!(matches!(
+            parse("pause delete 4"),
+            Command::PauseDelete((4, false))
+        ));
+        assert!(matches!(
+            parse("pause delete 4 --yes"),
+            Command::PauseDelete((4, true))
+        ));
+        assert!(matches!(
+            parse("pause update 4 start 2026-10-19T12:00:00+02:00"),
+            Command::PauseUpdateStart((4, ref start)) if start == "2026-10-19T12:00:00+02:00"
+        ));
+        assert!(matches!(
+            parse("pause update 4 end 2026-10-19T12:30:00+02:00"),
+            Command::PauseUpdateEnd((4, ref end)) if end == "2026-10-19T12:30:00+02:00"
+        ));
+        assert!(matches!(
+            parse("pause note 4 Mittag"),
+            Command::PauseNote((4, ref note)) if note == "Mittag"
+        ));
+    }
+
+    #[test]
+    fn report_commands() {
+        assert!(matches!(parse("status"), Command::Status(None)));
+        assert!(matches!(parse("feierabend"), Command::Feierabend));
+        assert!(matches!(parse("dashboard"), Command::Dashboard));
+        assert!(matches!(parse("report"), Command::Report((None, false))));
+        assert!(matches!(
+            parse("report 2026-10 --exact"),
+            Command::Report((Some((2026, 10)), true))
+        ));
+        assert!(matches!(
+            parse("projects"),
+            Command::Projects((None, false))
+        ));
+        assert!(matches!(
+            parse("projects 2026-03"),
+            Command::Projects((Some((2026, 3)), false))
+        ));
+        assert!(matches!(
+            parse("earnings"),
+            Command::Earnings((None, false))
+        ));
+        assert!(matches!(
+            parse("earnings 2026-12 --exact"),
+            Command::Earnings((Some((2026, 12)), true))
+        ));
+        assert!(matches!(
+            parse("homeoffice"),
+            Command::Homeoffice((None, false))
+        ));
+        assert!(matches!(
+            parse("homeoffice 2026-01"),
+            Command::Homeoffice((Some((2026, 1)), false))
+        ));
+        assert!(matches!(parse("tax"), Command::Tax((None, false, false))));
+        assert!(matches!(
+            parse("tax 2026 --csv --exact"),
+            Command::Tax((Some(2026), true, true))
+        ));
+        assert!(matches!(
+            parse("export csv"),
+            Command::Export((Format::Csv, None, false))
+        ));
+        assert!(matches!(
+            parse("export json out.json --exact"),
+            Command::Export((Format::Json, Some(ref file), true)) if file == "out.json"
+        ));
+        assert!(matches!(
+            parse_err("report 2026-13"),
+            ParseError::UnknownVerb { .. }
+        ));
+    }
+
+    #[test]
+    fn absence_and_other_commands() {
+        let day = |day| NaiveDate::from_ymd_opt(2026, 12, day).unwrap();
+        assert!(matches!(
+            parse("absence add vacation 2026-12-22..2026-12-31"),
+            Command::AbsenceAdd((Kind::Vacation, from, to, false)) if from == day(22) && to == day(31)
+        ));
+        assert!(matches!(
+            parse("absence add sick 2026-12-01"),
+            Command::AbsenceAdd((Kind::Sick, from, to, false)) if from == day(1) && to == day(1)
+        ));
+        assert!(matches!(
+            parse("absence add comp 2026-12-24 --half"),
+            Command::AbsenceAdd((Kind::Comp, _, _, true))
+        ));
+        assert!(matches!(parse("absence list"), Command::AbsenceList));
+        assert!(matches!(
+            parse("absence delete 7"),
+            Command::AbsenceDelete(7)
+        ));
+        assert!(matches!(parse("undo"), Command::Undo));
+        assert!(matches!(parse("help"), Command::Help(None)));
+        assert!(matches!(
+            parse("help block"),
+            Command::Help(Some(ref topic)) if topic == "block"
+        ));
+        assert!(matches!(parse("exit"), Command::Exit));
+        assert!(matches!(
+            parse_err("absence add vacation 2026-12-31..2026-12-01"),
+            ParseError::UnknownVerb { .. }
+        ));
+    }
+
+    fn aliases(definitions: &[(&str, &str)]) -> HashMap<String, String> {
+        definitions
+            .iter()
+            .map(|(name, definition)| (name.to_string(), definition.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn aliases_take_precedence_over_commands() {
+        let aliases = aliases(&[
+            ("feierabend", "pause end; block end"),
+            ("status", "status --format '{time}'"),
+            ("a", "b"),
+            ("b", "a"),
+        ]);
+
+        let commands = parse_line("feierabend", &aliases).unwrap_or_else(|_| panic!("alias"));
+        assert!(matches!(
+            commands.as_slice(),
+            [Command::PauseEnd, Command::BlockEnd]
+        ));
+
+        let commands = parse_line("'feierabend'", &aliases).unwrap_or_else(|_| panic!("quoted"));
+        assert!(matches!(commands.as_slice(), [Command::Feierabend]));
+
+        let commands = parse_line("status", &aliases).unwrap_or_else(|_| panic!("own command"));
+        assert!(matches!(
+            commands.as_slice(),
+            [Command::Status(Some(ref format))] if format == "{time}"
+        ));
+
+        assert!(matches!(
+            parse_line("a", &aliases),
+            Err(ParseError::AliasLoop(_))
+        ));
     }
 }
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Local, Utc};
+
+use crate::{
+    absence::Absences,
+    auth::Token,
+    config::Config,
+    fetch::ActionHandler,
+    i18n::Msg,
+    time::Duration,
+    work::{
+        fetch_with_current_block, paused_on, required_pause, worked_between, MAX_WORKING_HOURS,
+        PAUSE_RULES,
+    },
+};
+
+/// Prints when today's target, the pause thresholds and the maximum working
+/// time will be reached if work continues now without further pauses than
+/// the legally required ones.
+pub fn print_projection(
+    token: &Token,
+    config: &Config,
+    action_handler: &mut ActionHandler,
+) -> bool {
+    let Some(blocks) = fetch_with_current_block(token, action_handler) else {
+        return false;
+    };
+
+    let now = Utc::now();
+    let today = config.display.today();
+    let worked = worked_between(&blocks, today, today, &config.display, now);
+    let paused = paused_on(&blocks, today, &config.display, now);
+
+    // The time at which `total` is worked, including the pause still missing
+    // for it.
+    let reached_at = |total: chrono::Duration| -> Option<DateTime<Utc>> {
+        let missing_pause = (required_pause(total) - paused).max(chrono::Duration::zero());
+        (total > worked).then(|| now + (total - worked) + missing_pause)
+    };
+    // Projected times get the offset of the latest block, like stored times.
+    let offset = blocks.last().map_or(*Local::now().offset(), |block| {
+        block.start_time().timezone()
+    });
+    let time = |date_time: Option<DateTime<Utc>>| match date_time {
+        Some(date_time) => config.display.time(date_time.with_timezone(&offset)),
+        None => Msg::AlreadyReached.to_string(),
+    };
+    let format = |duration| Duration::from_chrono_duration(duration).hours_minutes();
+
+    let target = Absences::load().open_target(today, &config.work);
+    println!("{}", Msg::WorkedToday(&format(worked), &format(paused)));
+    println!(
+        "{}",
+        Msg::TargetAt(&format(target), &time(reached_at(target)))
+    );
+
+    for (hours, minutes) in PAUSE_RULES {
+        let threshold = chrono::Duration::hours(*hours);
+        println!(
+            "{}",
+            Msg::PauseThresholdAt(*hours, *minutes, &time(reached_at(threshold)))
+        );
+    }
+
+    println!(
+        "{}",
+        Msg::MaximumAt(
+            MAX_WORKING_HOURS,
+            &time(reached_at(chrono::Duration::hours(MAX_WORKING_HOURS)))
+        )
+    );
+
+    let missing = required_pause(target.max(worked)) - paused;
+    if missing > chrono::Duration::zero() {
+        println!("{}", Msg::PauseMissing(&format(missing)));
+    }
+
+    true
+}
@@ -0,0 +1,121 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use std::collections::BTreeMap;
+
+use crate::{
+    auth::Token,
+    block::Block,
+    config::Config,
+    fetch::{ActionHandler, ResponseHandler},
+    i18n::Msg,
+    rounding::RoundingConfig,
+    time::Duration,
+};
+
+/// Sums the rounded working time of the blocks per group, a block counts for
+/// each of its groups.
+fn time_per<'a>(
+    blocks: &'a [Block],
+    groups: impl Fn(&'a Block) -> Vec<Option<&'a str>>,
+    config: &Config,
+    rounding: &RoundingConfig,
+) -> BTreeMap<Option<&'a str>, chrono::Duration> {
+    let now = Utc::now();
+
+    let mut per_day: BTreeMap<(Option<&str>, NaiveDate), Vec<chrono::Duration>> = BTreeMap::new();
+    for block in blocks {
+        let day = config.display.day(block.start_time());
+        for group in groups(block) {
+            per_day
+                .entry((group, day))
+                .or_default()
+                .push(block.net_time(now));
+        }
+    }
+
+    let mut totals: BTreeMap<Option<&str>, chrono::Duration> = BTreeMap::new();
+    for ((group, _), times) in per_day {
+        *totals.entry(group).or_default() += rounding.total(times);
+    }
+    totals
+}
+
+/// Prints the working time per project and per tag of a month.
+pub fn print_projects(
+    month: Option<(i32, u32)>,
+    exact: bool,
+    token: &Token,
+    config: &Config,
+    action_handler: &mut ActionHandler,
+) -> bool {
+    let today = config.display.today();
+    let (year, month) = month.unwrap_or((today.year(), today.month()));
+
+    let Some(blocks) = action_handler
+        .get_all_blocks(token)
+        .handle_response(Msg::ProjectsTitle(year, month), Msg::NoBlocks)
+    else {
+        return false;
+    };
+
+    let blocks: Vec<Block> = blocks
+        .into_iter()
+        .filter(|block| {
+            let day = config.display.day(block.start_time());
+            day.year() == year && day.month() == month
+        })
+        .collect();
+
+    let rounding = config.rounding.unless(exact);
+    let format = |duration| Duration::from_chrono_duration(duration).hours_minutes();
+
+    let projects = time_per(
+        &blocks,
+        |block| vec![block.project.as_deref()],
+        config,
+        &rounding,
+    );
+    let total = projects
+        .values()
+        .fold(chrono::Duration::zero(), |sum, time| sum + *time);
+
+    let no_project = Msg::NoProject.to_string();
+    let width = projects
+        .keys()
+        .map(|project| project.unwrap_or(&no_project).chars().count())
+        .max()
+        .unwrap_or(0);
+
+    for (project, time) in &projects {
+        let share = match total.num_seconds() {
+            0 => 0.0,
+            seconds => time.num_seconds() as f64 * 100.0 / seconds as f64,
+        };
+        println!(
+            "{:<width$}  {}  {share:>3.0} %",
+            project.unwrap_or(&no_project),
+            format(*time)
+        );
+    }
+
+    let tags = time_per(
+        &blocks,
+        |block| block.tags.iter().map(|tag| Some(tag.as_str())).collect(),
+        config,
+        &rounding,
+    );
+    if !tags.is_empty() {
+        println!();
+        println!("{}", Msg::TagsTitle);
+        let width = tags
+            .keys()
+            .flatten()
+            .map(|tag| tag.chars().count())
+            .max()
+            .unwrap_or(0);
+        for (tag, time) in tags {
+            println!("{:<width$}  {}", tag.unwrap_or_default(), format(time));
+        }
+    }
+
+    true
+}
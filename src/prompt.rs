@@ -1,30 +1,138 @@
 use colored::{ColoredString, Colorize};
-use std::io::stdin;
+use rustyline::{error::ReadlineError, history::FileHistory, Config, Editor};
+use std::io::{stdin, IsTerminal};
 
-pub fn prompt_login() -> (String, String) {
-    println!("Gib deine Email-Adresse ein:");
+use crate::{complete::CommandHelper, data::data_file_path, i18n::Msg};
+
+const HISTORY_FILE: &str = "history";
+const HISTORY_SIZE: usize = 1000;
+
+pub fn prompt_email() -> String {
+    println!("{}", Msg::PromptEmail);
     let mut email = String::new();
     stdin()
         .read_line(&mut email)
         .expect("Fehler beim Lesen des Inputs");
 
-    println!("Gib dein Passwort ein:");
+    email.trim().to_string()
+}
+
+pub fn prompt_password() -> String {
+    println!("{}", Msg::PromptPassword);
     let mut password = String::new();
     stdin()
         .read_line(&mut password)
         .expect("Fehler beim Lesen des Inputs");
 
-    (email.trim().to_string(), password.trim().to_string())
+    password.trim().to_string()
+}
+
+/// Asks a yes or no question, anything but yes counts as no.
+pub fn confirm(question: Msg) -> bool {
+    println!("{question}");
+    let mut answer = String::new();
+    if stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+
+    matches!(
+        answer.trim().to_lowercase().as_str(),
+        "j" | "ja" | "y" | "yes"
+    )
+}
+
+/// Reads commands with line editing, history and tab completion if a
+/// terminal is attached and plain lines from stdin otherwise.
+pub struct CommandPrompt {
+    editor: Option<Editor<CommandHelper, FileHistory>>,
+    history_path: Option<String>,
 }
 
-pub fn prompt_command() -> String {
-    println!("> Gib ein Kommando ein:");
+impl CommandPrompt {
+    pub fn new() -> CommandPrompt {
+        if !is_interactive() {
+            return CommandPrompt {
+                editor: None,
+                history_path: None,
+            };
+        }
+
+        let config = Config::builder()
+            .auto_add_history(true)
+            .history_ignore_space(true)
+            .history_ignore_dups(true)
+            .and_then(|config| config.max_history_size(HISTORY_SIZE))
+            .expect("Fehler beim Konfigurieren der Eingabe")
+            .build();
+
+        let mut editor = Editor::with_config(config).ok();
+        let history_path = data_file_path(HISTORY_FILE).ok();
+
+        if let Some(ref mut editor) = editor {
+            editor.set_helper(Some(CommandHelper::default()));
+            if let Some(ref path) = history_path {
+                let _ = editor.load_history(path);
+            }
+        }
+
+        CommandPrompt {
+            editor,
+            history_path,
+        }
+    }
+
+    /// Updates the IDs offered by the tab completion.
+    pub fn set_ids(&mut self, block_ids: Vec<i32>, pause_ids: Vec<i32>) {
+        if let Some(helper) = self.editor.as_mut().and_then(|editor| editor.helper_mut()) {
+            helper.set_ids(block_ids, pause_ids);
+        }
+    }
+
+    /// Updates the aliases offered by the tab completion.
+    pub fn set_aliases(&mut self, aliases: Vec<String>) {
+        if let Some(helper) = self.editor.as_mut().and_then(|editor| editor.helper_mut()) {
+            helper.set_aliases(aliases);
+        }
+    }
+
+    /// Returns `None` once the input is closed.
+    pub fn prompt_command(&mut self) -> Option<String> {
+        let Some(ref mut editor) = self.editor else {
+            return read_line_from_stdin();
+        };
+
+        println!("{}", Msg::PromptCommand);
+        loop {
+            match editor.readline("> ") {
+                Ok(command) => {
+                    if let Some(ref path) = self.history_path {
+                        let _ = editor.save_history(path);
+                    }
+                    return Some(command);
+                }
+                Err(ReadlineError::Interrupted) => continue,
+                Err(ReadlineError::Eof) => return None,
+                Err(err) => panic!("Fehler beim Lesen des Inputs: {err}"),
+            }
+        }
+    }
+}
+
+fn read_line_from_stdin() -> Option<String> {
     let mut command = String::new();
-    stdin()
+    let read = stdin()
         .read_line(&mut command)
         .expect("Fehler beim Lesen des Inputs");
 
-    command
+    if read == 0 {
+        return None;
+    }
+
+    Some(command)
+}
+
+pub fn is_interactive() -> bool {
+    stdin().is_terminal()
 }
 
 pub fn error_text(s: &str) -> ColoredString {
@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::{collections::HashSet, io::Write};
+
+use crate::{
+    absence::Absences, block::Block, config::Config, i18n::Msg, prompt::success_text,
+    time::HoursMinutes, work::worked_between,
+};
+
+/// How often the current block is checked.
+pub const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    /// Today's working time without pauses reached `after`.
+    Worked,
+    /// Worked for `after` since the start of the block or the last pause.
+    WithoutPause,
+    /// Today's working time reached the daily target.
+    Target,
+}
+
+/// An entry of the `[[reminders]]` list in the config.
+#[derive(Deserialize)]
+pub struct Reminder {
+    pub trigger: Trigger,
+    pub after: Option<HoursMinutes>,
+    #[serde(default)]
+    pub bell: bool,
+    pub message: Option<String>,
+    /// Run with `sh -c`, the trigger is passed in `WORK_HOURS_REMINDER`.
+    pub command: Option<String>,
+}
+
+/// Remembers which reminders already fired, so each fires once per occasion.
+#[derive(Default)]
+pub struct Reminders {
+    fired: HashSet<String>,
+}
+
+impl Reminders {
+    /// Fires all reminders that are due for the current block, the blocks are
+    /// used for today's working time.
+    pub fn check(&mut self, current: &Block, blocks: &[Block], config: &Config) {
+        let now = Utc::now();
+
+        for (i, reminder) in config.reminders.iter().enumerate() {
+            let Some(occasion) = due(reminder, current, blocks, config, now) else {
+                continue;
+            };
+            if self.fired.insert(format!("{i}/{occasion}")) {
+                fire(reminder, config);
+            }
+        }
+    }
+}
+
+/// Returns a key identifying the occasion if the reminder is due.
+fn due(
+    reminder: &Reminder,
+    current: &Block,
+    blocks: &[Block],
+    config: &Config,
+    now: DateTime<Utc>,
+) -> Option<String> {
+    let today = config.display.today();
+    let after = reminder.after.map(|after| after.0);
+
+    match reminder.trigger {
+        Trigger::Worked | Trigger::Target => {
+            let threshold = match reminder.trigger {
+                Trigger::Target => Some(Absences::load().open_target(today, &config.work))
+                    .filter(|target| *target > chrono::Duration::zero())?,
+                _ => after?,
+            };
+            let worked = worked_between(blocks, today, today, &config.display, now);
+            (worked >= threshold).then(|| today.to_string())
+        }
+        Trigger::WithoutPause => {
+            if current.active_pause().is_some() {
+                return None;
+            }
+            let since = current
+                .pauses()
+                .iter()
+                .filter_map(|pause| pause.end_time())
+                .max()
+                .unwrap_or(current.start_time());
+            (now.signed_duration_since(since) >= after?).then(|| since.to_rfc3339())
+        }
+    }
+}
+
+fn fire(reminder: &Reminder, config: &Config) {
+    if reminder.bell {
+        print!("\x07");
+    }
+
+    let message = match reminder.message {
+        Some(ref message) => Some(message.clone()),
+        None if !reminder.bell && reminder.command.is_none() => {
+            Some(default_message(reminder, config))
+        }
+        None => None,
+    };
+    if let Some(message) = message {
+        println!("{}", success_text(&format!("> {message}")));
+    }
+    let _ = std::io::stdout().flush();
+
+    if let Some(ref command) = reminder.command {
+        let child = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .env("WORK_HOURS_REMINDER", trigger_name(reminder.trigger))
+            .spawn();
+        // Reap the process without waiting for it.
+        if let Ok(mut child) = child {
+            std::thread::spawn(move || child.wait());
+        }
+    }
+}
+
+fn default_message(reminder: &Reminder, config: &Config) -> String {
+    let format = |duration: HoursMinutes| {
+        crate::time::Duration::from_chrono_duration(duration.0).hours_minutes()
+    };
+    let after = reminder.after.map(format).unwrap_or_default();
+
+    match reminder.trigger {
+        Trigger::Worked => Msg::ReminderWorked(&after).to_string(),
+        Trigger::WithoutPause => Msg::ReminderWithoutPause(&after).to_string(),
+        Trigger::Target => Msg::ReminderTarget(&format(HoursMinutes(
+            Absences::load().open_target(config.display.today(), &config.work),
+        )))
+        .to_string(),
+    }
+}
+
+fn trigger_name(trigger: Trigger) -> &'static str {
+    match trigger {
+        Trigger::Worked => "worked",
+        Trigger::WithoutPause => "without_pause",
+        Trigger::Target => "target",
+    }
+}
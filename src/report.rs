@@ -0,0 +1,116 @@
+use chrono::{Datelike, NaiveDate, Utc};
+
+use crate::{
+    absence::{Absence, Absences},
+    auth::Token,
+    config::Config,
+    fetch::{ActionHandler, ResponseHandler},
+    holidays::holiday,
+    i18n::Msg,
+    time::{signed_hours_minutes, Duration},
+    work::block_times_on,
+};
+
+/// A day of the report.
+struct Day<'a> {
+    date: NaiveDate,
+    worked: chrono::Duration,
+    /// Target time credited for vacation and sick leave.
+    credited: chrono::Duration,
+    target: chrono::Duration,
+    holiday: Option<&'static str>,
+    absence: Option<&'a Absence>,
+}
+
+impl Day<'_> {
+    fn difference(&self) -> chrono::Duration {
+        self.worked + self.credited - self.target
+    }
+
+    /// Name of the holiday or the absence.
+    fn note(&self) -> String {
+        match (self.holiday, self.absence) {
+            (Some(holiday), _) => holiday.to_string(),
+            (None, Some(absence)) => absence.label(),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// Prints the working time, target and difference of each day of a month up
+/// to today, followed by the totals.
+pub fn print_report(
+    month: Option<(i32, u32)>,
+    exact: bool,
+    token: &Token,
+    config: &Config,
+    action_handler: &mut ActionHandler,
+) -> bool {
+    let today = config.display.today();
+    let (year, month) = month.unwrap_or((today.year(), today.month()));
+    let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+        return false;
+    };
+
+    let Some(blocks) = action_handler
+        .get_all_blocks(token)
+        .handle_response(Msg::ReportTitle(year, month), Msg::NoBlocks)
+    else {
+        return false;
+    };
+
+    let absences = Absences::load();
+    let rounding = config.rounding.unless(exact);
+    let now = Utc::now();
+    let days: Vec<Day> = first
+        .iter_days()
+        .take_while(|date| date.month() == month && *date <= today)
+        .map(|date| Day {
+            date,
+            // Whole minutes, so that the columns add up.
+            worked: chrono::Duration::minutes(
+                rounding
+                    .total(block_times_on(&blocks, date, &config.display, now))
+                    .num_minutes(),
+            ),
+            credited: absences.credited_on(date, &config.work),
+            target: config.work.target_on(date),
+            holiday: holiday(date, config.work.state),
+            absence: absences.on(date),
+        })
+        .collect();
+
+    let format = |duration| Duration::from_chrono_duration(duration).hours_minutes();
+    let zero = chrono::Duration::zero();
+
+    for day in &days {
+        if day.worked == zero && day.target == zero && day.note().is_empty() {
+            continue;
+        }
+        println!(
+            "{} {}  {}  {}  {}  {}",
+            Msg::Weekday(day.date.weekday()),
+            config.display.day_date(day.date),
+            format(day.worked),
+            format(day.target),
+            signed_hours_minutes(day.difference()),
+            day.note()
+        );
+    }
+
+    let worked = days.iter().fold(zero, |sum, day| sum + day.worked);
+    let credited = days.iter().fold(zero, |sum, day| sum + day.credited);
+    let target = days.iter().fold(zero, |sum, day| sum + day.target);
+    println!();
+    println!(
+        "{}",
+        Msg::ReportTotal(
+            &format(worked),
+            &format(credited),
+            &format(target),
+            &signed_hours_minutes(worked + credited - target)
+        )
+    );
+
+    true
+}
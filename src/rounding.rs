@@ -0,0 +1,169 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    None,
+    Nearest,
+    Up,
+    Down,
+}
+
+/// Whether each block or the total of a day is rounded.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Per {
+    Block,
+    Day,
+}
+
+/// How working time is rounded in reports and exports, the `[rounding]`
+/// section of the config.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct RoundingConfig {
+    pub mode: Mode,
+    pub minutes: i64,
+    pub per: Per,
+}
+
+impl Default for RoundingConfig {
+    fn default() -> RoundingConfig {
+        RoundingConfig {
+            mode: Mode::None,
+            minutes: 15,
+            per: Per::Day,
+        }
+    }
+}
+
+impl RoundingConfig {
+    /// The rounding to use, none if the exact times were asked for.
+    pub fn unless(self, exact: bool) -> RoundingConfig {
+        match exact {
+            true => RoundingConfig::default(),
+            false => self,
+        }
+    }
+
+    fn round(&self, duration: chrono::Duration) -> chrono::Duration {
+        let step = self.minutes * 60;
+        if step <= 0 {
+            return duration;
+        }
+
+        let seconds = duration.num_seconds();
+        let steps = match self.mode {
+            Mode::None => return duration,
+            Mode::Nearest => (seconds + step / 2).div_euclid(step),
+            Mode::Up => (seconds + step - 1).div_euclid(step),
+            Mode::Down => seconds.div_euclid(step),
+        };
+        chrono::Duration::seconds(steps * step)
+    }
+
+    /// Rounds the working time of a single block, if blocks are rounded.
+    pub fn block(&self, duration: chrono::Duration) -> chrono::Duration {
+        match self.per {
+            Per::Block => self.round(duration),
+            Per::Day => duration,
+        }
+    }
+
+    /// Rounds the working time of a day, if days are rounded.
+    pub fn day(&self, duration: chrono::Duration) -> chrono::Duration {
+        match self.per {
+            Per::Block => duration,
+            Per::Day => self.round(duration),
+        }
+    }
+
+    /// The working time of a day made up of the given block times.
+    pub fn total(&self, durations: impl IntoIterator<Item = chrono::Duration>) -> chrono::Duration {
+        self.day(
+            durations
+                .into_iter()
+                .map(|duration| self.block(duration))
+                .fold(chrono::Duration::zero(), |sum, duration| sum + duration),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rounding(mode: Mode, per: Per) -> RoundingConfig {
+        RoundingConfig {
+            mode,
+            minutes: 15,
+            per,
+        }
+    }
+
+    fn minutes(minutes: i64, seconds: i64) -> chrono::Duration {
+        chrono::Duration::minutes(minutes) + chrono::Duration::seconds(seconds)
+    }
+
+    #[test]
+    fn round_to_the_nearest_step() {
+        let rounding = rounding(Mode::Nearest, Per::Day);
+
+        assert_eq!(rounding.round(minutes(7, 29)), minutes(0, 0));
+        assert_eq!(rounding.round(minutes(7, 30)), minutes(15, 0));
+        assert_eq!(rounding.round(minutes(22, 29)), minutes(15, 0));
+        assert_eq!(rounding.round(minutes(22, 30)), minutes(30, 0));
+        assert_eq!(rounding.round(minutes(30, 0)), minutes(30, 0));
+    }
+
+    #[test]
+    fn round_up_and_down() {
+        let up = rounding(Mode::Up, Per::Day);
+        let down = rounding(Mode::Down, Per::Day);
+
+        assert_eq!(up.round(minutes(0, 1)), minutes(15, 0));
+        assert_eq!(up.round(minutes(15, 0)), minutes(15, 0));
+        assert_eq!(up.round(minutes(15, 1)), minutes(30, 0));
+        assert_eq!(down.round(minutes(14, 59)), minutes(0, 0));
+        assert_eq!(down.round(minutes(15, 0)), minutes(15, 0));
+        assert_eq!(down.round(minutes(29, 59)), minutes(15, 0));
+    }
+
+    #[test]
+    fn no_rounding() {
+        let exact = rounding(Mode::Nearest, Per::Day).unless(true);
+        let no_step = RoundingConfig {
+            minutes: 0,
+            ..rounding(Mode::Up, Per::Day)
+        };
+
+        assert_eq!(exact.round(minutes(7, 30)), minutes(7, 30));
+        assert_eq!(no_step.round(minutes(7, 30)), minutes(7, 30));
+        assert_eq!(
+            rounding(Mode::None, Per::Day).round(minutes(7, 30)),
+            minutes(7, 30)
+        );
+    }
+
+    #[test]
+    fn round_each_block_or_the_day() {
+        let blocks = [minutes(8, 0), minutes(8, 0)];
+
+        assert_eq!(
+            rounding(Mode::Nearest, Per::Block).total(blocks),
+            minutes(30, 0)
+        );
+        assert_eq!(
+            rounding(Mode::Nearest, Per::Day).total(blocks),
+            minutes(15, 0)
+        );
+        assert_eq!(
+            rounding(Mode::Nearest, Per::Block).day(minutes(8, 0)),
+            minutes(8, 0)
+        );
+        assert_eq!(
+            rounding(Mode::Nearest, Per::Day).block(minutes(8, 0)),
+            minutes(8, 0)
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use crate::{
+    args::OPTIONS,
+    i18n::Msg,
+    parse::{topics, Arg, COMMANDS},
+};
+
+const BIN_NAME: &str = "work_hours_cli";
+const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+/// Commands only available on the command line, not in the interactive prompt.
+const CLI_COMMANDS: &[(&str, &[&str])] = &[
+    ("completions", SHELLS),
+    ("run", &["--stop-on-error"]),
+    ("remind", &[]),
+];
+
+/// Generates the completion script of a shell. Aliases are left out, the
+/// script would not follow later changes of the config.
+pub fn generate_completions(shell: &str) -> Result<String, String> {
+    let rules = completion_rules();
+
+    match shell {
+        "bash" => Ok(bash_script(&rules)),
+        "zsh" => Ok(zsh_script(&rules)),
+        "fish" => Ok(fish_script(&rules)),
+        _ => Err(Msg::UnknownShell(shell, &SHELLS.join(", ")).to_string()),
+    }
+}
+
+/// A glob pattern over the preceding words and the words allowed after them.
+/// Each `*` stands for one word, so the number of words is matched as well.
+struct Rule {
+    words: usize,
+    pattern: String,
+    candidates: Vec<String>,
+}
+
+/// Derives the completion rules from the command definitions of the parser.
+fn completion_rules() -> Vec<Rule> {
+    let mut rules: Vec<Rule> = Vec::new();
+
+    let mut add = |prefix: &[String], candidates: Vec<String>| {
+        if candidates.is_empty() {
+            return;
+        }
+        let pattern = prefix.join(" ");
+        match rules.iter_mut().find(|rule| rule.pattern == pattern) {
+            Some(rule) => {
+                for candidate in candidates {
+                    if !rule.candidates.contains(&candidate) {
+                        rule.candidates.push(candidate);
+                    }
+                }
+            }
+            None => rules.push(Rule {
+                words: prefix.len(),
+                pattern,
+                candidates,
+            }),
+        }
+    };
+
+    add(
+        &[],
+        OPTIONS
+            .iter()
+            .map(|(option, _)| option.to_string())
+            .collect(),
+    );
+
+    for spec in COMMANDS {
+        let mut prefix: Vec<String> = Vec::new();
+
+        for word in spec.words {
+            add(&prefix, vec![word.to_string()]);
+            prefix.push(word.to_string());
+        }
+
+        for arg in spec.args {
+            let (candidates, pattern) = match arg {
+                Arg::Keyword(keyword) => (vec![keyword.to_string()], keyword.to_string()),
+                Arg::Bool => (
+                    vec![String::from("true"), String::from("false")],
+                    "*".into(),
+                ),
+                Arg::Topic => (
+                    topics().iter().map(|topic| topic.to_string()).collect(),
+                    "*".into(),
+                ),
+                Arg::BlockId
+                | Arg::PauseId
+                | Arg::AbsenceId
+                | Arg::Project
+                | Arg::Tag
+                | Arg::Text
+                | Arg::File
+                | Arg::Date
+                | Arg::Year
+                | Arg::Month
+                | Arg::Days => (Vec::new(), "*".into()),
+            };
+            add(&prefix, candidates);
+            prefix.push(pattern);
+        }
+
+        add(
+            &prefix,
+            spec.flags
+                .iter()
+                .map(|flag| format!("--{}", flag.name))
+                .collect(),
+        );
+    }
+
+    for (command, args) in CLI_COMMANDS {
+        add(&[], vec![command.to_string()]);
+        add(
+            &[command.to_string()],
+            args.iter().map(|arg| arg.to_string()).collect(),
+        );
+    }
+
+    rules
+}
+
+/// Case pattern matching the options before the command that take a
+/// command as value.
+fn command_options() -> String {
+    OPTIONS
+        .iter()
+        .filter(|(_, takes_value)| *takes_value)
+        .map(|(option, _)| option.to_string())
+        .collect::<Vec<String>>()
+        .join("|")
+}
+
+/// Case pattern matching all options and flags that take a value, which is
+/// skipped when counting the words.
+fn value_options() -> String {
+    let mut options: Vec<String> = OPTIONS
+        .iter()
+        .filter(|(_, takes_value)| *takes_value)
+        .map(|(option, _)| option.to_string())
+        .collect();
+
+    let flags = COMMANDS
+        .iter()
+        .flat_map(|spec| spec.flags)
+        .filter(|flag| flag.takes_value)
+        .map(|flag| format!("--{}", flag.name));
+    for flag in flags {
+        if !options.contains(&flag) {
+            options.push(flag);
+        }
+    }
+
+    options.join("|")
+}
+
+/// Case pattern over the number of preceding words and the words, e.g.
+/// `3:"block update "*""`.
+fn case_pattern(rule: &Rule) -> String {
+    format!("{}:\"{}\"", rule.words, rule.pattern.replace('*', "\"*\""))
+}
+
+fn bash_script(rules: &[Rule]) -> String {
+    let mut cases = String::new();
+    for rule in rules {
+        cases += &format!(
+            "        {}) words=\"{}\" ;;\n",
+            case_pattern(rule),
+            rule.candidates.join(" ")
+        );
+    }
+
+    format!(
+        r#"_{BIN_NAME}() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    local line=""
+    local count=0
+    local words=""
+    local i
+
+    case "$prev" in
+        {value_options}) return ;;
+    esac
+
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        case "${{COMP_WORDS[i]}}" in
+            {value_options}) ((i++)) ;;
+            -*) ;;
+            *)
+                line="${{line:+$line }}${{COMP_WORDS[i]}}"
+                ((count++))
+                ;;
+        esac
+    done
+
+    case "$count:$line" in
+{cases}    esac
+
+    COMPREPLY=($(compgen -W "$words" -- "$cur"))
+}}
+
+complete -F _{BIN_NAME} {BIN_NAME}
+"#,
+        value_options = value_options(),
+    )
+}
+
+fn zsh_script(rules: &[Rule]) -> String {
+    let mut cases = String::new();
+    for rule in rules {
+        cases += &format!(
+            "        {}) candidates=({}) ;;\n",
+            case_pattern(rule),
+            rule.candidates.join(" ")
+        );
+    }
+
+    format!(
+        r#"#compdef {BIN_NAME}
+
+_{BIN_NAME}() {{
+    local line=""
+    local count=0
+    local -a candidates
+    local i
+
+    case "${{words[CURRENT-1]}}" in
+        {command_options}) _command_names -e; return ;;
+        {value_options}) return ;;
+    esac
+
+    for ((i = 2; i < CURRENT; i++)); do
+        case "${{words[i]}}" in
+            {value_options}) ((i++)) ;;
+            -*) ;;
+            *)
+                line="${{line:+$line }}${{words[i]}}"
+                ((count++))
+                ;;
+        esac
+    done
+
+    case "$count:$line" in
+{cases}    esac
+
+    compadd -a candidates
+}}
+
+compdef _{BIN_NAME} {BIN_NAME}
+"#,
+        command_options = command_options(),
+        value_options = value_options(),
+    )
+}
+
+fn fish_script(rules: &[Rule]) -> String {
+    let mut script = format!(
+        r#"function __{BIN_NAME}_prefix
+    set -l tokens (commandline -opc)
+    set -e tokens[1]
+    set -l words
+    set -l skip 0
+    for token in $tokens
+        if test $skip -eq 1
+            set skip 0
+        else if contains -- $token {fish_value_options}
+            set skip 1
+        else if not string match -q -- '-*' $token
+            set -a words $token
+        end
+    end
+    test (count $words) -eq $argv[1]
+    and string match -q -- $argv[2] "$words"
+end
+
+complete -c {BIN_NAME} -f
+"#,
+        fish_value_options = value_options().replace('|', " "),
+    );
+
+    for rule in rules {
+        script += &format!(
+            "complete -c {BIN_NAME} -n \"__{BIN_NAME}_prefix {} '{}'\" -a '{}'\n",
+            rule.words,
+            rule.pattern,
+            rule.candidates.join(" ")
+        );
+    }
+
+    script
+}
@@ -0,0 +1,156 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::Token, block::Block, config::Config, data::data_file_path, error::FetchError,
+    fetch::ActionHandler, i18n::Msg, prompt::error_text, time::Duration,
+};
+
+const CACHE_FILE: &str = "status.json";
+
+/// Settings of the `status` command, the `[status]` section of the config.
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct StatusConfig {
+    pub format: String,
+    /// Printed instead of the format while no block is active.
+    pub inactive: String,
+    /// How long the current block is read from the cache instead of the server.
+    pub cache_seconds: i64,
+}
+
+impl Default for StatusConfig {
+    fn default() -> StatusConfig {
+        StatusConfig {
+            format: String::from("{state} {homeoffice} {time} (Pause {pause})"),
+            inactive: String::from("○"),
+            cache_seconds: 10,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Cache {
+    fetched_at: i64,
+    block: Option<Block>,
+}
+
+/// Prints the current block as a single line, for status bars and prompts.
+pub fn print_status(
+    format: Option<&str>,
+    config: &Config,
+    token: &Token,
+    action_handler: &mut ActionHandler,
+) -> bool {
+    let block = match current_block(config.status.cache_seconds, token, action_handler) {
+        Ok(block) => block,
+        Err(_) => {
+            eprintln!("{}", error_text(&Msg::NetworkError.to_string()));
+            return false;
+        }
+    };
+
+    print_line(format, block.as_ref(), config);
+    true
+}
+
+/// Prints the status from the cache, without logging in. Returns `false` if
+/// the cache is missing or too old.
+pub fn print_cached_status(format: Option<&str>, config: &Config) -> bool {
+    let Some(block) = cached_block(config.status.cache_seconds) else {
+        return false;
+    };
+
+    print_line(format, block.as_ref(), config);
+    true
+}
+
+fn print_line(format: Option<&str>, block: Option<&Block>, config: &Config) {
+    match block {
+        Some(block) => {
+            let format = format.unwrap_or(&config.status.format);
+            println!("{}", render(format, block, config));
+        }
+        None => println!("{}", config.status.inactive),
+    }
+}
+
+/// Replaces the placeholders of the format with the values of the block.
+fn render(format: &str, block: &Block, config: &Config) -> String {
+    let now = Utc::now();
+    let hours_minutes = |duration| Duration::from_chrono_duration(duration).hours_minutes();
+
+    let state = match block.active_pause() {
+        Some(_) => "⏸",
+        None => "●",
+    };
+    let homeoffice = match block.homeoffice() {
+        true => "HO",
+        false => "",
+    };
+
+    let line = format
+        .replace("{state}", state)
+        .replace("{homeoffice}", homeoffice)
+        .replace("{time}", &hours_minutes(block.net_time(now)))
+        .replace("{gross}", &hours_minutes(block.gross_time(now)))
+        .replace("{pause}", &hours_minutes(block.pause_time(now)))
+        .replace("{start}", &config.display.time(block.start_time()))
+        .replace("{id}", &block.id.to_string());
+
+    // Empty placeholders must not leave gaps.
+    line.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Returns the current block from the cache if it is recent enough, from the
+/// server otherwise.
+fn current_block(
+    max_age: i64,
+    token: &Token,
+    action_handler: &mut ActionHandler,
+) -> Result<Option<Block>, FetchError> {
+    if let Some(block) = cached_block(max_age) {
+        return Ok(block);
+    }
+
+    let block = match action_handler.get_current_block(token) {
+        Ok((block, status)) => status.is_success().then_some(block),
+        Err(FetchError::JSONError(_)) => None,
+        Err(err) => return Err(err),
+    };
+
+    write_cache(&Cache {
+        fetched_at: Utc::now().timestamp(),
+        block: block.clone(),
+    });
+
+    Ok(block)
+}
+
+/// The cached current block, if it is younger than `max_age` seconds.
+fn cached_block(max_age: i64) -> Option<Option<Block>> {
+    let cache = read_cache()?;
+    (Utc::now().timestamp() - cache.fetched_at < max_age).then_some(cache.block)
+}
+
+fn read_cache() -> Option<Cache> {
+    let path = data_file_path(CACHE_FILE).ok()?;
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn write_cache(cache: &Cache) {
+    let Ok(path) = data_file_path(CACHE_FILE) else {
+        return;
+    };
+    if let Ok(content) = serde_json::to_string(cache) {
+        let _ = std::fs::write(path, content);
+    }
+}
+
+/// Removes the cache after the block or its pauses changed.
+pub fn clear_cache() {
+    if let Ok(path) = data_file_path(CACHE_FILE) {
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::{
+    auth::Token,
+    config::Config,
+    fetch::{ActionHandler, ResponseHandler},
+    homeoffice::place_days,
+    i18n::Msg,
+    time::Duration,
+};
+
+/// Settings of the `tax` command, the `[tax]` section of the config.
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct TaxConfig {
+    /// Homeoffice days per year that can be claimed.
+    pub homeoffice_day_cap: u32,
+    /// Amount claimed per homeoffice day, in euros.
+    pub homeoffice_rate: f64,
+}
+
+impl Default for TaxConfig {
+    fn default() -> TaxConfig {
+        TaxConfig {
+            homeoffice_day_cap: 210,
+            homeoffice_rate: 6.0,
+        }
+    }
+}
+
+/// Prints the office and homeoffice days of a year for the tax return, per
+/// month or as CSV with one line per day.
+pub fn print_tax(
+    year: Option<i32>,
+    csv: bool,
+    exact: bool,
+    token: &Token,
+    config: &Config,
+    action_handler: &mut ActionHandler,
+) -> bool {
+    let year = year.unwrap_or(config.display.today().year());
+    let (Some(first), Some(last)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        return false;
+    };
+
+    let blocks = match csv {
+        // The title would end up in the CSV.
+        true => match action_handler.get_all_blocks(token) {
+            Ok((blocks, status)) if status.is_success() => Some(blocks),
+            _ => None,
+        },
+        false => action_handler
+            .get_all_blocks(token)
+            .handle_response(Msg::TaxTitle(year), Msg::NoBlocks),
+    };
+    let Some(blocks) = blocks else {
+        return false;
+    };
+
+    let days = place_days(
+        &blocks,
+        first,
+        last,
+        &config.display,
+        &config.rounding.unless(exact),
+        Utc::now(),
+    );
+    let format = |duration| Duration::from_chrono_duration(duration).hours_minutes();
+
+    if csv {
+        println!("date,place,office,homeoffice");
+        for (day, place) in &days {
+            let name = match place.in_office {
+                true => "office",
+                false => "homeoffice",
+            };
+            println!(
+                "{},{name},{},{}",
+                day.format("%Y-%m-%d"),
+                format(place.office_time),
+                format(place.homeoffice_time)
+            );
+        }
+        return true;
+    }
+
+    for month in 1..=12 {
+        let (office, homeoffice) = days.iter().filter(|(day, _)| day.month() == month).fold(
+            (0, 0),
+            |(office, homeoffice), (_, place)| match place.in_office {
+                true => (office + 1, homeoffice),
+                false => (office, homeoffice + 1),
+            },
+        );
+        if office + homeoffice == 0 {
+            continue;
+        }
+        println!(
+            "{}  {}",
+            Msg::MonthOfYear(year, month),
+            Msg::TaxDays(
+                &Msg::DayCount(office).to_string(),
+                &Msg::DayCount(homeoffice).to_string()
+            )
+        );
+    }
+
+    let office = days.values().filter(|place| place.in_office).count() as u32;
+    let homeoffice = days.len() as u32 - office;
+    let claimed = homeoffice.min(config.tax.homeoffice_day_cap);
+
+    println!();
+    println!(
+        "{}",
+        Msg::TaxDays(
+            &Msg::DayCount(office).to_string(),
+            &Msg::DayCount(homeoffice).to_string()
+        )
+    );
+    println!(
+        "{}",
+        Msg::TaxHomeofficeAllowance(
+            &Msg::DayCount(claimed).to_string(),
+            config.tax.homeoffice_day_cap,
+            &Msg::Euros(claimed as f64 * config.tax.homeoffice_rate).to_string()
+        )
+    );
+
+    true
+}
@@ -1,3 +1,5 @@
+use serde::Deserialize;
+
 pub struct Duration {
     pub hours: i64,
     pub minutes: i64,
@@ -18,4 +20,54 @@ impl Duration {
             seconds,
         }
     }
+
+    /// `HH:MM`, for compact output.
+    pub fn hours_minutes(&self) -> String {
+        format!("{:0>2}:{:0>2}", self.hours, self.minutes)
+    }
+}
+
+impl std::fmt::Display for Duration {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{:0>2}:{:0>2}:{:0>2}",
+            self.hours, self.minutes, self.seconds
+        )
+    }
+}
+
+/// `+HH:MM` or `-HH:MM`, for balances.
+pub fn signed_hours_minutes(duration: chrono::Duration) -> String {
+    let sign = if duration < chrono::Duration::zero() {
+        "-"
+    } else {
+        "+"
+    };
+    format!(
+        "{sign}{}",
+        Duration::from_chrono_duration(duration.abs()).hours_minutes()
+    )
+}
+
+/// A duration written as `HH:MM` in the config.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(try_from = "String")]
+pub struct HoursMinutes(pub chrono::Duration);
+
+impl TryFrom<String> for HoursMinutes {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<HoursMinutes, String> {
+        let invalid = || format!("invalid duration '{value}', expected HH:MM");
+        let (hours, minutes) = value.split_once(':').ok_or_else(invalid)?;
+        let hours: i64 = hours.parse().map_err(|_| invalid())?;
+        let minutes: i64 = minutes.parse().map_err(|_| invalid())?;
+        if minutes >= 60 {
+            return Err(invalid());
+        }
+        Ok(HoursMinutes(
+            chrono::Duration::hours(hours) + chrono::Duration::minutes(minutes),
+        ))
+    }
 }
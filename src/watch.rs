@@ -0,0 +1,142 @@
+use chrono::Utc;
+use ratatui::crossterm::{
+    cursor::{MoveToColumn, MoveUp},
+    event::{self, Event, KeyEventKind},
+    execute,
+    terminal::{self, Clear, ClearType},
+};
+use std::{
+    io::{stdout, Write},
+    time::Instant,
+};
+
+use crate::{
+    absence::Absences,
+    auth::Token,
+    block::Block,
+    config::Config,
+    fetch::{ActionHandler, ResponseHandler},
+    i18n::Msg,
+    prompt::error_text,
+    time::Duration,
+    work::{fetch_with_current_block, with_current_block, worked_between},
+};
+
+const TICK: std::time::Duration = std::time::Duration::from_secs(1);
+const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Redraws the current block every second until a key is pressed or the
+/// block is no longer active.
+pub fn watch_current_block(
+    token: &Token,
+    config: &Config,
+    action_handler: &mut ActionHandler,
+) -> bool {
+    let Some(mut blocks) = fetch_with_current_block(token, action_handler) else {
+        return false;
+    };
+
+    if let Err(err) = terminal::enable_raw_mode() {
+        println!("{}", error_text(&format!("> {err}")));
+        return false;
+    }
+
+    let absences = Absences::load();
+
+    // The message why watching stopped, if it was not a key press.
+    let result = (|| -> std::io::Result<Option<String>> {
+        let mut refreshed_at = Instant::now();
+        let mut drawn_lines = 0;
+
+        loop {
+            let lines = watch_lines(&blocks, &absences, config);
+
+            let mut out = stdout();
+            if drawn_lines > 0 {
+                execute!(out, MoveUp(drawn_lines))?;
+            }
+            execute!(out, MoveToColumn(0), Clear(ClearType::FromCursorDown))?;
+            for line in &lines {
+                write!(out, "{line}\r\n")?;
+            }
+            out.flush()?;
+            drawn_lines = lines.len() as u16;
+
+            if event::poll(TICK)? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        return Ok(None);
+                    }
+                }
+            }
+
+            if refreshed_at.elapsed() >= REFRESH_INTERVAL {
+                action_handler.clear_cache();
+                let (current, message) = action_handler
+                    .get_current_block(token)
+                    .response_message(Msg::CurrentBlock, Msg::NoBlockActive);
+                let Some(current) = current else {
+                    return Ok(Some(message));
+                };
+                blocks = with_current_block(current, token, action_handler);
+                refreshed_at = Instant::now();
+            }
+        }
+    })();
+
+    let _ = terminal::disable_raw_mode();
+
+    match result {
+        Ok(None) => true,
+        Ok(Some(message)) => {
+            println!("{}", error_text(&message));
+            false
+        }
+        Err(err) => {
+            println!("{}", error_text(&format!("> {err}")));
+            false
+        }
+    }
+}
+
+fn watch_lines(blocks: &[Block], absences: &Absences, config: &Config) -> Vec<String> {
+    let now = Utc::now();
+    let format = |duration| Duration::from_chrono_duration(duration).to_string();
+
+    let Some(block) = blocks.last() else {
+        return Vec::new();
+    };
+
+    let mut lines = vec![
+        Msg::BlockActive(block.id).to_string(),
+        Msg::Homeoffice(block.homeoffice()).to_string(),
+        Msg::ActiveSince(&config.display.date_time(block.start_time())).to_string(),
+        Msg::Time(&format(block.gross_time(now))).to_string(),
+        Msg::NetTime(&format(block.net_time(now))).to_string(),
+    ];
+
+    if let Some(pause) = block.active_pause() {
+        lines.push(format!(
+            "{}: {}",
+            Msg::PauseActive(pause.id),
+            format(pause.duration(now))
+        ));
+    }
+
+    let today = config.display.today();
+    let worked = worked_between(blocks, today, today, &config.display, now);
+    let remaining = absences.open_target(today, &config.work) - worked;
+
+    if remaining > chrono::Duration::zero() {
+        lines.push(Msg::TargetReachedIn(&format(remaining)).to_string());
+        // In the offset of the block, like its stored times.
+        let end = (now + remaining).with_timezone(&block.start_time().timezone());
+        lines.push(Msg::ProjectedEnd(&config.display.time(end)).to_string());
+    } else {
+        lines.push(Msg::TargetExceededBy(&format(-remaining)).to_string());
+    }
+
+    lines.push(String::new());
+    lines.push(Msg::PressAnyKey.to_string());
+    lines
+}
@@ -0,0 +1,157 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+use serde::Deserialize;
+
+use crate::{
+    auth::Token,
+    block::Block,
+    display::DisplayConfig,
+    fetch::{ActionHandler, ResponseHandler},
+    holidays::{holiday, State},
+    i18n::Msg,
+    time::HoursMinutes,
+};
+
+/// Minimum pauses required by the Arbeitszeitgesetz once the working time
+/// exceeds the given hours.
+pub const PAUSE_RULES: &[(i64, i64)] = &[(6, 30), (9, 45)];
+/// Maximum working time per day allowed by the Arbeitszeitgesetz.
+pub const MAX_WORKING_HOURS: i64 = 10;
+
+/// Working time rules, the `[work]` section of the config.
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct WorkConfig {
+    /// Working time without pauses expected per day.
+    pub daily_target: HoursMinutes,
+    /// The federal state whose public holidays are days off.
+    pub state: Option<State>,
+    /// Vacation days per year.
+    pub vacation_days: Option<u32>,
+    /// Maximum percentage of homeoffice days per month.
+    pub homeoffice_quota: Option<u32>,
+}
+
+impl Default for WorkConfig {
+    fn default() -> WorkConfig {
+        WorkConfig {
+            daily_target: HoursMinutes(chrono::Duration::hours(8)),
+            state: None,
+            vacation_days: None,
+            homeoffice_quota: None,
+        }
+    }
+}
+
+impl WorkConfig {
+    /// The working time expected on a day, nothing on weekends and public
+    /// holidays.
+    pub fn target_on(&self, day: NaiveDate) -> chrono::Duration {
+        let weekend = matches!(day.weekday(), Weekday::Sat | Weekday::Sun);
+        if weekend || holiday(day, self.state).is_some() {
+            return chrono::Duration::zero();
+        }
+        self.daily_target.0
+    }
+}
+
+/// Working time without pauses of the blocks started between `from` and `to`,
+/// both inclusive, counting active blocks and pauses up to `now`.
+pub fn worked_between(
+    blocks: &[Block],
+    from: NaiveDate,
+    to: NaiveDate,
+    display: &DisplayConfig,
+    now: DateTime<Utc>,
+) -> chrono::Duration {
+    blocks
+        .iter()
+        .filter(|block| (from..=to).contains(&display.day(block.start_time())))
+        .map(|block| block.net_time(now))
+        .fold(chrono::Duration::zero(), |sum, duration| sum + duration)
+}
+
+/// Working times without pauses of the blocks started on a day, counting
+/// active blocks and pauses up to `now`.
+pub fn block_times_on<'a>(
+    blocks: &'a [Block],
+    day: NaiveDate,
+    display: &'a DisplayConfig,
+    now: DateTime<Utc>,
+) -> impl Iterator<Item = chrono::Duration> + 'a {
+    blocks
+        .iter()
+        .filter(move |block| display.day(block.start_time()) == day)
+        .map(move |block| block.net_time(now))
+}
+
+/// Time not worked on a day, the pauses within the blocks as well as the gaps
+/// between them.
+pub fn paused_on(
+    blocks: &[Block],
+    day: NaiveDate,
+    display: &DisplayConfig,
+    now: DateTime<Utc>,
+) -> chrono::Duration {
+    let mut blocks: Vec<&Block> = blocks
+        .iter()
+        .filter(|block| display.day(block.start_time()) == day)
+        .collect();
+    blocks.sort_by_key(|block| block.start_time());
+
+    let pauses = blocks
+        .iter()
+        .map(|block| block.pause_time(now))
+        .fold(chrono::Duration::zero(), |sum, duration| sum + duration);
+
+    let gaps = blocks
+        .windows(2)
+        .filter_map(|pair| {
+            let gap = pair[1]
+                .start_time()
+                .signed_duration_since(pair[0].end_time()?);
+            (gap > chrono::Duration::zero()).then_some(gap)
+        })
+        .fold(chrono::Duration::zero(), |sum, duration| sum + duration);
+
+    pauses + gaps
+}
+
+/// The pause required for a working time by `PAUSE_RULES`.
+pub fn required_pause(worked: chrono::Duration) -> chrono::Duration {
+    PAUSE_RULES
+        .iter()
+        .filter(|(hours, _)| worked > chrono::Duration::hours(*hours))
+        .map(|(_, minutes)| chrono::Duration::minutes(*minutes))
+        .max()
+        .unwrap_or_else(chrono::Duration::zero)
+}
+
+/// Fetches all blocks with the current block last, `None` if no block is
+/// active.
+pub fn fetch_with_current_block(
+    token: &Token,
+    action_handler: &mut ActionHandler,
+) -> Option<Vec<Block>> {
+    action_handler.clear_cache();
+
+    let current = action_handler
+        .get_current_block(token)
+        .handle_response(Msg::CurrentBlock, Msg::NoBlockActive)?;
+
+    Some(with_current_block(current, token, action_handler))
+}
+
+/// Fetches all blocks and puts the current block last.
+pub fn with_current_block(
+    current: Block,
+    token: &Token,
+    action_handler: &mut ActionHandler,
+) -> Vec<Block> {
+    let mut blocks: Vec<Block> = match action_handler.get_all_blocks(token) {
+        Ok((blocks, status)) if status.is_success() => blocks,
+        _ => Vec::new(),
+    };
+    blocks.retain(|block| block.id != current.id);
+    blocks.push(current);
+    blocks
+}